@@ -0,0 +1,57 @@
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::{fs, path::PathBuf};
+
+use crate::{Args, ColorChoice, Commands};
+
+/// Default values for global options, loaded from a TOML file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub tld_cache: Option<String>,
+    pub color: Option<ColorChoice>,
+    pub raw: Option<bool>,
+    pub no_pager: Option<bool>,
+    pub concurrency: Option<usize>,
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    let proj_dirs = ProjectDirs::from("com", "peterc-s", "harper")?;
+    Some(proj_dirs.config_dir().join("config.toml"))
+}
+
+/// Load the config from the given path, or the default location if it exists.
+pub fn load_config(path: Option<&str>) -> Result<Config> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => match default_config_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        },
+    };
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file: {:?}", path))?;
+
+    // toml errors already name the offending key
+    toml::from_str(&contents).with_context(|| format!("Invalid config file: {:?}", path))
+}
+
+/// Fill in any options not given on the command line from the config.
+pub fn apply_config(args: &mut Args, config: Config) {
+    args.tld_cache = args.tld_cache.take().or(config.tld_cache);
+    args.color = args.color.or(config.color);
+
+    // --no-raw and --pager only exist to override the config
+    if !args.raw && !args.no_raw {
+        args.raw = config.raw.unwrap_or(false);
+    }
+    if !args.no_pager && !args.pager {
+        args.no_pager = config.no_pager.unwrap_or(false);
+    }
+
+    if let Commands::Redirects(redirect_args) = &mut args.command {
+        redirect_args.concurrency = redirect_args.concurrency.or(config.concurrency);
+    }
+}
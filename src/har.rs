@@ -64,9 +64,18 @@ pub struct Entry {
     // leniancy given, shouldn't be optional.
     #[serde(deserialize_with = "deserialize_empty_object")]
     pub timings: Option<Timing>,
+    #[serde(rename = "serverIPAddress")]
     pub server_ip_address: Option<String>,
     pub connection: Option<String>,
     pub comment: Option<String>,
+    // chrome's record of what triggered the request, kept as-is as other
+    // tools use the name for different shapes.
+    #[serde(rename = "_initiator")]
+    pub initiator: Option<Value>,
+    // position in the capture as loaded, not part of the HAR, so the entries
+    // left after filtering can be found again in the raw document.
+    #[serde(skip)]
+    pub index: usize,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -0,0 +1,22 @@
+//! Analysis of HTTP Archive (HAR) captures.
+//!
+//! This is the library behind the `harper` command line tool. Deserialize a
+//! capture into a [`Har`] with serde, then pass it to the functions in each
+//! analysis module, i.e. [`count_schemes::get_counts`] or
+//! [`search_for::search_for`].
+
+pub mod har;
+pub mod ops;
+
+pub use har::Har;
+pub use ops::{
+    annotate, bloat, blocklist, body, bottleneck, budget, bytes, cache_control, chunked, comments,
+    compression, connections, cookie_audit, cookie_flow, cookie_stats, cookies, cors,
+    count_requests, count_schemes, count_urls, counts, critical_path, decode, devtools_summary,
+    dns, dns_timings, domain_lists, duplicate_headers, export_postman, filter, find_json, fmt,
+    graph, gzippable, hashes, host_mimes, host_timings, https_audit, initiators, list_domains,
+    long_query, merge, mime, mime_mismatch, no_cache, pages, ports, private_ips, query_params,
+    rate, rate_limit, redact, redirects, retries, search_for, serialization, size_ratio,
+    soft_errors, stats, third_party, timing, ttfb, url_check, urls, user_agents, validate,
+    where_param,
+};
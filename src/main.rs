@@ -1,51 +1,279 @@
 use anyhow::{anyhow, Context, Result};
-use base64::{prelude::BASE64_STANDARD_NO_PAD, Engine};
-use chrono::{DateTime, Local};
+use base64::{
+    prelude::{BASE64_STANDARD, BASE64_STANDARD_NO_PAD, BASE64_URL_SAFE, BASE64_URL_SAFE_NO_PAD},
+    Engine,
+};
 use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
 use colored::Colorize;
+use flate2::read::MultiGzDecoder;
 use serde_json::{self, error::Category};
 use std::{
-    cmp::Reverse,
-    collections::HashMap,
+    collections::{btree_map, BTreeMap},
     fs,
-    io::{self, IsTerminal, Read},
+    io::{self, BufRead, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
     process::ExitCode,
+    time::{Duration, Instant},
 };
-use tldextract::TldOption;
+use tldextract::{TldExtractor, TldOption};
 
-mod ops;
-use ops::{
-    blocklist, count_requests, count_schemes, count_urls, dns, filter, list_domains, search_for,
+use harper::{
+    annotate, bloat, blocklist, body, bottleneck, budget, cache_control, chunked, comments,
+    compression, connections, cookie_audit, cookie_flow, cookie_stats, cors, count_requests,
+    count_schemes, count_urls, critical_path, decode, devtools_summary, dns, dns_timings,
+    domain_lists, duplicate_headers, export_postman, filter, find_json, fmt, graph, gzippable,
+    hashes, host_mimes, host_timings, https_audit, initiators, list_domains, long_query, merge,
+    mime_mismatch, no_cache, pages, ports, private_ips, rate, rate_limit, redact, redirects,
+    retries, search_for, serialization, size_ratio, soft_errors, third_party, ttfb, url_check,
+    urls, user_agents, validate, where_param, Har,
 };
 
-mod har;
-use har::Har;
+mod pager;
+
+mod render;
+
+mod config;
+
+mod schema;
 
 #[derive(Parser, Debug)]
 #[command(version, about = "Command line HAR analyser.", long_about = None)]
 struct Args {
-    #[arg(short, long, help = "Filters out requests after the time.", default_value = None, global = true)]
-    before: Option<DateTime<Local>>,
+    #[arg(short, long, help = "Keeps only requests starting at or before the time.", default_value = None, global = true)]
+    before: Option<filter::TimeArg>,
+
+    #[arg(short, long, help = "Keeps only requests starting at or after the time.", default_value = None, global = true)]
+    after: Option<filter::TimeArg>,
+
+    #[arg(long, help = "IANA timezone for times given without an offset, i.e. Europe/London. [default: local]", default_value = None, global = true)]
+    timezone: Option<chrono_tz::Tz>,
+
+    #[arg(short, long, help = "Keeps only entries with indices in the range A:B, i.e. 100:150, :50 or 100:.", default_value = None, global = true)]
+    range: Option<filter::IndexRange>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Keeps only entries after request number N, as printed by commands like SearchFor.",
+        global = true
+    )]
+    after_request: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Keeps only entries before request number N, as printed by commands like SearchFor.",
+        global = true
+    )]
+    before_request: Option<usize>,
+
+    #[arg(long, help = "Keeps only entries with a response bodySize in the inclusive range MIN:MAX bytes, i.e. 100000:1000000 or 100000:.", default_value = None, global = true)]
+    size_range: Option<filter::SizeRange>,
+
+    #[arg(long, help = "Keeps only the entries around the first one containing the string.", default_value = None, global = true)]
+    around_match: Option<String>,
+
+    #[arg(
+        long,
+        help = "Number of entries kept either side of --around-match.",
+        default_value_t = 5,
+        requires = "around_match",
+        global = true
+    )]
+    window: usize,
+
+    #[arg(long, help = "Keeps only entries where a timing phase exceeds a threshold in ms, i.e. dns:100.", default_value = None, global = true)]
+    slow_phase: Option<filter::PhaseThreshold>,
+
+    #[arg(
+        long,
+        value_name = "NAME[:VALUE]",
+        help = "Keeps only entries whose request or response has the header, optionally with the value. Can be repeated, entries must have all of them.",
+        global = true
+    )]
+    has_header: Vec<filter::HeaderMatch>,
+
+    #[arg(
+        long,
+        help = "Keeps only entries with a non-empty body, dropping redirects, 204s and empty responses.",
+        global = true
+    )]
+    only_with_body: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Which body --only-with-body looks at.",
+        default_value_t = body::BodySide::Response,
+        requires = "only_with_body",
+        global = true
+    )]
+    body_side: body::BodySide,
+
+    #[arg(
+        long,
+        help = "Print exact sizes and durations instead of human readable ones, and binary bodies as-is.",
+        overrides_with = "no_raw",
+        global = true
+    )]
+    raw: bool,
+
+    #[arg(
+        long,
+        help = "Print human readable sizes and durations, overriding raw in the config.",
+        overrides_with = "raw",
+        global = true
+    )]
+    no_raw: bool,
+
+    #[arg(
+        long,
+        help = "Print how many entries each filter keeps to stderr.",
+        global = true
+    )]
+    explain_filters: bool,
+
+    #[arg(
+        long,
+        help = "Fail with a report of every entry that doesn't follow the spec, instead of skipping what's missing.",
+        global = true
+    )]
+    strict: bool,
+
+    #[arg(
+        long,
+        help = "Log internal diagnostics to stderr at this level, i.e. debug.",
+        global = true
+    )]
+    log_level: Option<log::LevelFilter>,
+
+    #[arg(
+        short,
+        long,
+        help = "Don't report an empty capture to stderr.",
+        global = true
+    )]
+    quiet: bool,
+
+    #[arg(
+        long,
+        help = "Print results as JSON. Output, Entry, Body, Redact, ExportPostman and Graph are unaffected.",
+        global = true
+    )]
+    json: bool,
+
+    #[arg(
+        long,
+        help = "Parse entries as the input is read rather than buffering it first. Output, Entry and Redact aren't supported.",
+        global = true
+    )]
+    stream: bool,
+
+    #[arg(
+        long,
+        help = "Also load HARs from subdirectories when FILE is a directory.",
+        global = true
+    )]
+    recursive: bool,
+
+    #[arg(
+        long,
+        help = "Name of the input shown in errors, i.e. when piping. [default: the file path or stdin]",
+        global = true
+    )]
+    name: Option<String>,
+
+    #[arg(
+        long,
+        help = "Never pipe long output through $PAGER.",
+        overrides_with = "pager",
+        global = true
+    )]
+    no_pager: bool,
+
+    #[arg(
+        long,
+        help = "Pipe long output through $PAGER, overriding no-pager in the config.",
+        overrides_with = "no_pager",
+        global = true
+    )]
+    pager: bool,
+
+    #[arg(
+        long,
+        help = "Path of the TLD cache used by CountUrls and ThirdParty. [default: .tld_cache]",
+        global = true
+    )]
+    tld_cache: Option<String>,
 
-    #[arg(short, long, help = "Filters out requests before the time.", default_value = None, global = true)]
-    after: Option<DateTime<Local>>,
+    #[arg(long, help = "When to colour output. [default: auto]", global = true)]
+    color: Option<ColorChoice>,
+
+    #[arg(
+        long,
+        env = "HARPER_CONFIG",
+        help = "Config file with default option values, defaults to config.toml in the harper config directory.",
+        global = true
+    )]
+    config: Option<String>,
+
+    #[arg(
+        long,
+        hide = true,
+        default_value_t = 1,
+        help = "Run the command this many times, printing timings to stderr.",
+        global = true
+    )]
+    repeat: usize,
 
     #[clap(subcommand)]
     command: Commands,
 
-    /// Input HAR file (use '-' for stdin).
-    #[arg(default_value = "-", required_unless_present_any = ["get-block-lists"])]
+    /// Input HAR file, .har.gz file or directory of them to merge (use '-' for stdin).
+    #[arg(default_value = "-")]
     file: String,
 }
 
+impl Args {
+    /// Name of the input for messages, `None` for commands that don't read one.
+    fn input_label(&self) -> Option<String> {
+        if matches!(
+            self.command,
+            Commands::GetBlockLists | Commands::RemoveBlockLists | Commands::Schema(_)
+        ) {
+            return None;
+        }
+
+        Some(match (&self.name, self.file.as_str()) {
+            (Some(name), _) => name.clone(),
+            (None, "-") => "stdin".to_string(),
+            (None, file) => file.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ColorChoice {
+    /// Colour when writing to a terminal, unless NO_COLOR is set.
+    Auto,
+
+    /// Always colour output.
+    Always,
+
+    /// Never colour output.
+    Never,
+}
+
 #[derive(Subcommand, Debug)]
 #[command(arg_required_else_help = true)]
 enum Commands {
     /// Count number of times a request is sent to a URL.
     CountUrls(CountUrlArgs),
 
-    /// Lists domains in the HAR.
-    ListDomains,
+    /// List the unique domains in the HAR, sorted so subdomains sit together.
+    #[command(visible_alias = "domains")]
+    ListDomains(ListDomainsArgs),
 
     /// Count number of each scheme in the HAR.
     CountSchemes,
@@ -57,10 +285,10 @@ enum Commands {
     SearchFor(SearchForArgs),
 
     /// Return the contents of the HAR.
-    Output,
+    Output(OutputArgs),
 
     /// Check if URLs contained in the HAR are using DNSSEC.
-    DNSSECAudit,
+    DNSSECAudit(DnssecAuditArgs),
 
     /// Lookup common DNS record types of URLs contained in the HAR.
     DNSLookup,
@@ -73,6 +301,150 @@ enum Commands {
 
     /// Checks for URLs in common blocklists.
     BlockList,
+
+    /// Export the requests as a Postman v2.1 collection.
+    ExportPostman,
+
+    /// List requests carrying a specific query parameter.
+    WhereParam(WhereParamArgs),
+
+    /// Report requests with oversized headers or cookies.
+    Bloat(BloatArgs),
+
+    /// Count requests sent over each connection.
+    Connections,
+
+    /// Flag requests served from private, loopback or link-local addresses.
+    PrivateIps,
+
+    /// Aggregate request times per host.
+    HostTimings,
+
+    /// Flag responses whose mime type doesn't match the URL extension.
+    MimeMismatch,
+
+    /// Search JSON bodies for a key.
+    FindJson(FindJsonArgs),
+
+    /// Compare how often each cookie is set by the server and sent by the client.
+    CookieFlow,
+
+    /// List the distinct request URLs.
+    Urls(UrlsArgs),
+
+    /// Audit cookies for common problems.
+    CookieAudit(CookieAuditArgs),
+
+    /// List the requests with the longest time to first byte.
+    Ttfb(TtfbArgs),
+
+    /// Return a single entry of the HAR.
+    Entry(EntryArgs),
+
+    /// Count redirect chains by how they redirected.
+    Redirects(RedirectsArgs),
+
+    /// Compare transferred and decoded body sizes per mime type.
+    Compression,
+
+    /// Flag 200 responses whose body looks like an error page.
+    SoftErrors(SoftErrorsArgs),
+
+    /// Print a summary like the footer of the browser devtools network panel.
+    DevtoolsSummary,
+
+    /// Count requests started in each time interval.
+    Rate(RateArgs),
+
+    /// Flag chunked responses and responses without a content length.
+    Chunked,
+
+    /// Output a Graphviz DOT graph of pages, resources and hosts.
+    Graph(GraphArgs),
+
+    /// Count requests with and without cookies and find the largest cookie header.
+    CookieStats,
+
+    /// Tally Cache-Control directives and max-age values across responses.
+    CacheControl,
+
+    /// Count requests to ports other than 80 and 443, by host and port.
+    Ports,
+
+    /// Find headers repeated with conflicting values in a request or response.
+    DuplicateHeaders,
+
+    /// Check transferred bytes against a page weight budget, failing if over.
+    Budget(BudgetArgs),
+
+    /// List the comments left in the capture and where they are.
+    Comments,
+
+    /// Flag entries with negative timings or phases that exceed the total time.
+    TimingCheck,
+
+    /// List the mime types served by each host, by request volume.
+    HostMimes,
+
+    /// Find http:// requests to hosts also served over https://.
+    HttpsAudit,
+
+    /// Print the decoded body of an entry, or a hex dump if it's binary.
+    Body(BodyArgs),
+
+    /// Find URLs with unusually long query strings or many parameters.
+    LongQuery(LongQueryArgs),
+
+    /// Output the HAR with credentials, cookies and bodies redacted.
+    Redact(RedactArgs),
+
+    /// Pair CORS preflights with their requests and check they're allowed.
+    Cors,
+
+    /// Find static assets served without Cache-Control or Expires headers.
+    NoCache,
+
+    /// Count the requests sent with each User-Agent.
+    UserAgents,
+
+    /// Find failed requests that were retried, with the gaps between attempts.
+    Retries,
+
+    /// Print the JSON schema of each command's --json output.
+    Schema(SchemaArgs),
+
+    /// Find requests that waited for each other on a connection and could have run in parallel.
+    Serialization(SerializationArgs),
+
+    /// Classify the domains requested as first party, third party or trackers.
+    ThirdParty(ThirdPartyArgs),
+
+    /// Find textual responses sent without compression.
+    Gzippable(GzippableArgs),
+
+    /// Find small requests with huge responses, or huge requests with tiny responses.
+    SizeRatio(SizeRatioArgs),
+
+    /// Estimate the chain of requests that held up each page's onLoad event.
+    CriticalPath,
+
+    /// Aggregate the DNS lookup times recorded in the capture per host.
+    DnsTimings(DnsTimingsArgs),
+
+    /// Count what triggered the requests, from Chrome's initiator records.
+    Initiators(InitiatorsArgs),
+
+    /// Hash response bodies to find identical content served from different URLs.
+    Hashes(HashesArgs),
+
+    /// Count which timing phase each request spent the most time in.
+    Bottleneck(BottleneckArgs),
+
+    /// Flag request URLs that don't parse or look malformed.
+    UrlCheck,
+
+    /// Find hosts that rate limited requests and the rate that set them off.
+    RateLimit(RateLimitArgs),
 }
 
 #[derive(Debug, clap::Args)]
@@ -86,6 +458,27 @@ struct CountUrlArgs {
         help = "Merge the tld and the sld, i.e. merge example and .com"
     )]
     merge_tld: bool,
+    #[arg(short = 'w', long, help = "Merge www.example.com into example.com")]
+    merge_www: bool,
+
+    #[arg(
+        long,
+        help = "Count data: and blob: URLs under a (data-uri) node instead of skipping them"
+    )]
+    include_data_uris: bool,
+
+    #[arg(
+        long,
+        help = "Print only the total number of requests counted in the tree"
+    )]
+    total_only: bool,
+
+    #[arg(
+        long,
+        help = "Print each leaf as a dotted name with its count, i.e. ads.example.com: 30, instead of the tree",
+        conflicts_with = "total_only"
+    )]
+    flat: bool,
 }
 
 #[derive(Debug, Clone, clap::ValueEnum)]
@@ -93,39 +486,678 @@ enum SortBy {
     /// Sort alphanumerically at each level.
     Alpha,
 
-    /// Sort by frequency at each level.
-    Frequency,
+    /// Sort by frequency at each level.
+    Frequency,
+
+    /// Sort by the number of subdomain levels below each node, deepest first.
+    DomainLength,
+}
+
+impl AsRef<str> for SortBy {
+    fn as_ref(&self) -> &str {
+        match self {
+            SortBy::Frequency => "frequency",
+            SortBy::Alpha => "alpha",
+            SortBy::DomainLength => "domain-length",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+enum Base64Variant {
+    /// The standard alphabet with `=` padding.
+    Standard,
+
+    /// The standard alphabet without padding.
+    #[default]
+    StandardNopad,
+
+    /// The URL and filename safe alphabet with `=` padding.
+    Url,
+
+    /// The URL and filename safe alphabet without padding.
+    UrlNopad,
+}
+
+impl Base64Variant {
+    fn encode(&self, input: &str) -> String {
+        match self {
+            Base64Variant::Standard => BASE64_STANDARD.encode(input),
+            Base64Variant::StandardNopad => BASE64_STANDARD_NO_PAD.encode(input),
+            Base64Variant::Url => BASE64_URL_SAFE.encode(input),
+            Base64Variant::UrlNopad => BASE64_URL_SAFE_NO_PAD.encode(input),
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct ListDomainsArgs {
+    #[arg(short, long, help = "Show how many requests were made to each domain.")]
+    count: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct SearchForArgs {
+    /// The strings to search for.
+    #[arg(required = true)]
+    strings: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Report requests containing any of the strings. [default]"
+    )]
+    any: bool,
+
+    #[arg(
+        long,
+        help = "Only report requests containing all of the strings.",
+        conflicts_with = "any"
+    )]
+    all: bool,
+
+    #[arg(
+        short = 'v',
+        long,
+        help = "List requests that aren't matched by the strings."
+    )]
+    invert: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Only match the value at this path in JSON bodies, like $.data.token."
+    )]
+    json_path: Option<search_for::JsonPath>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Alphabet and padding of the base64 encoded search.",
+        default_value_t
+    )]
+    base64_variant: Base64Variant,
+
+    #[arg(short, long, help = "Also report hosts that nearly match the string.")]
+    fuzzy: bool,
+
+    #[arg(
+        long,
+        help = "Largest edit distance reported as a fuzzy match.",
+        default_value_t = 2,
+        requires = "fuzzy"
+    )]
+    max_distance: usize,
+
+    #[arg(
+        long,
+        help = "Template for each result, using {request_num}, {time}, {url}, {method}, {in_fields} and {needles}."
+    )]
+    format: Option<search_for::ResultTemplate>,
+
+    #[arg(
+        short,
+        long,
+        help = "Print one block per request, merging plain and base64 matches."
+    )]
+    group_by_request: bool,
+}
+
+impl SearchForArgs {
+    fn mode(&self) -> search_for::NeedleMatch {
+        if self.all {
+            search_for::NeedleMatch::All
+        } else {
+            search_for::NeedleMatch::Any
+        }
+    }
+
+    /// Requests matching the needles, in their JSON bodies if given a path.
+    fn search<'a>(&self, har: &'a Har, needles: &[String]) -> Vec<search_for::SearchResult<'a>> {
+        match &self.json_path {
+            Some(path) => search_for::search_json_path(har, path, needles, self.mode()),
+            None => search_for::search_for_needles(har, needles, self.mode()),
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct OutputArgs {
+    #[arg(short, long, help = "Decode base64 encoded response bodies into text.")]
+    decode_bodies: bool,
+
+    #[arg(
+        long,
+        help = "Group entries by page, listing each page's entries on stderr so the HAR stays valid."
+    )]
+    group_by_page: bool,
+
+    #[arg(
+        long,
+        help = "Add a _harper object to each entry with its party, registrable domain, decoded body length and timing sum."
+    )]
+    annotate: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct DnssecAuditArgs {
+    #[arg(
+        long,
+        help = "List the domains that would be checked without looking them up."
+    )]
+    list_only: bool,
+
+    #[arg(
+        long,
+        help = "Print only the number of domains using DNSSEC and failed lookups.",
+        conflicts_with = "list_only"
+    )]
+    summary_only: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct RedactArgs {
+    #[arg(long, help = "Keep request and response bodies.")]
+    keep_bodies: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct WhereParamArgs {
+    /// The parameter to look for, optionally with a value, i.e. name or name=value.
+    param: String,
+}
+
+#[derive(Debug, clap::Args)]
+struct BloatArgs {
+    #[arg(
+        short,
+        long,
+        help = "Size in bytes above which headers or cookies are reported.",
+        default_value_t = 8192
+    )]
+    threshold: u64,
+}
+
+#[derive(Debug, clap::Args)]
+struct FindJsonArgs {
+    /// The JSON key to search for.
+    key: String,
+}
+
+#[derive(Debug, clap::Args)]
+struct UrlsArgs {
+    #[arg(
+        short,
+        long,
+        help = "Prefix each URL with the number of requests to it."
+    )]
+    count: bool,
+
+    #[arg(
+        short,
+        long,
+        help = "Remove query strings and fragments before comparing URLs."
+    )]
+    strip_query: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct CookieAuditArgs {
+    #[arg(
+        long,
+        help = "Flag cookies that expire more than this many days after being set, Chrome caps them at 400.",
+        default_value_t = 400
+    )]
+    max_age_days: i64,
+}
+
+#[derive(Debug, clap::Args)]
+struct TtfbArgs {
+    #[arg(
+        short = 'n',
+        long,
+        help = "Number of requests to list.",
+        default_value_t = 10
+    )]
+    top: usize,
+}
+
+#[derive(Debug, clap::Args)]
+struct EntryArgs {
+    /// The request number of the entry, negative numbers count from the end.
+    #[arg(allow_negative_numbers = true)]
+    index: i64,
+}
+
+#[derive(Debug, clap::Args)]
+struct BodyArgs {
+    /// The request number of the entry, negative numbers count from the end.
+    #[arg(allow_negative_numbers = true)]
+    index: i64,
+
+    #[arg(long, help = "Print the request body.", conflicts_with = "response")]
+    request: bool,
+
+    #[arg(long, help = "Print the response body. [default]")]
+    response: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct LongQueryArgs {
+    #[arg(
+        short,
+        long,
+        help = "Query length in bytes above which URLs are reported.",
+        default_value_t = 512
+    )]
+    threshold: usize,
+
+    #[arg(
+        long,
+        help = "Number of parameters above which URLs are reported.",
+        default_value_t = 20
+    )]
+    max_params: usize,
+}
+
+#[derive(Debug, clap::Args)]
+struct RedirectsArgs {
+    #[arg(short, long, help = "List each redirect chain.")]
+    list: bool,
+
+    #[arg(
+        long,
+        help = "Send a HEAD request to each chain's final URL and compare its status with the capture."
+    )]
+    verify: bool,
+
+    #[arg(
+        long,
+        default_value_t = 10,
+        requires = "verify",
+        help = "Seconds to wait for each verification request."
+    )]
+    timeout: u64,
+
+    #[arg(
+        long,
+        requires = "verify",
+        help = "Maximum verification requests in flight at once. [default: 8]"
+    )]
+    concurrency: Option<usize>,
+}
+
+#[derive(Debug, clap::Args)]
+struct SoftErrorsArgs {
+    #[arg(
+        short,
+        long = "marker",
+        help = "Case-insensitive text marking an error page, can be repeated. Defaults to common error messages."
+    )]
+    markers: Vec<String>,
+}
+
+#[derive(Debug, clap::Args)]
+struct RateArgs {
+    #[arg(
+        short,
+        long,
+        help = "Width of each interval, i.e. 500ms, 1s, 1m or 1h.",
+        default_value = "1s"
+    )]
+    interval: rate::Interval,
+
+    #[arg(short, long, help = "Also print the counts as a sparkline.")]
+    sparkline: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct GraphArgs {
+    #[arg(
+        long,
+        value_enum,
+        default_value = "requests",
+        help = "What host nodes are sized by"
+    )]
+    size_by: graph::NodeSize,
+}
+
+#[derive(Debug, clap::Args)]
+struct BudgetArgs {
+    #[arg(long, help = "Budget in kilobytes (1024 bytes).")]
+    budget_kb: u64,
+
+    #[arg(
+        long,
+        help = "Check each page against the budget instead of the whole capture."
+    )]
+    per_page: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct SerializationArgs {
+    #[arg(
+        long,
+        help = "Milliseconds a connection can sit idle before the next request starts a new run.",
+        default_value_t = 1000.0
+    )]
+    max_gap: f64,
+
+    #[arg(
+        long,
+        help = "Milliseconds a run must be able to save to be reported.",
+        default_value_t = 50.0
+    )]
+    min_savings: f64,
+}
+
+#[derive(Debug, clap::Args)]
+struct ThirdPartyArgs {
+    #[arg(
+        long,
+        help = "File of first party registrable domains, one per line, as well as the first request's."
+    )]
+    allowlist: Option<String>,
+
+    #[arg(
+        long,
+        help = "File of known tracker registrable domains, one per line."
+    )]
+    blocklist: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+struct GzippableArgs {
+    #[arg(
+        long,
+        help = "Decoded body size in bytes from which responses are reported.",
+        default_value_t = 1024
+    )]
+    min_size: u64,
+}
+
+#[derive(Debug, clap::Args)]
+struct SizeRatioArgs {
+    #[arg(
+        long,
+        help = "How many times larger one side must be than the other to be reported.",
+        default_value_t = 50.0
+    )]
+    threshold: f64,
+
+    #[arg(
+        long,
+        help = "Bytes the larger side must have to be reported.",
+        default_value_t = 10240
+    )]
+    min_bytes: u64,
+}
+
+#[derive(Debug, clap::Args)]
+struct DnsTimingsArgs {
+    #[arg(
+        long,
+        help = "Flag hosts whose median lookup takes at least this many milliseconds.",
+        default_value_t = 100.0
+    )]
+    slow: f64,
+
+    #[arg(long, help = "Only list hosts with slow lookups.")]
+    slow_only: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct InitiatorsArgs {
+    #[arg(
+        short = 'n',
+        long,
+        help = "Number of triggering scripts to list.",
+        default_value_t = 10
+    )]
+    top: usize,
+}
+
+#[derive(Debug, clap::Args)]
+struct HashesArgs {
+    #[arg(
+        long,
+        help = "List the hash of every response body instead of the duplicates."
+    )]
+    all: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct BottleneckArgs {
+    #[arg(long, help = "Also list the slowest phase of each request.")]
+    requests: bool,
+}
+
+#[derive(Debug, clap::Args)]
+struct RateLimitArgs {
+    #[arg(
+        short,
+        long,
+        help = "Period before each 429 to measure the request rate over, i.e. 1s or 1m.",
+        default_value = "10s"
+    )]
+    period: rate::Interval,
+}
+
+#[derive(Debug, clap::Args)]
+struct SchemaArgs {
+    /// Only print the schema of this command, i.e. count-urls or "count-urls --total-only".
+    command: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    init_logger(args.log_level);
+    let label = args.input_label();
+
+    match run(args).await {
+        Ok(code) => code,
+        // the reader went away, i.e. output was piped into head
+        Err(e)
+            if e.downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            match label {
+                Some(label) => eprintln!("{}: {}: {:#}", "Error".red().bold(), label, e),
+                None => eprintln!("{}: {:#}", "Error".red().bold(), e),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_input(file_path: &str) -> Result<String> {
+    let mut bytes =
+        fs::read(file_path).with_context(|| format!("Failed to read file: {}", file_path))?;
+
+    if is_gzip(file_path) {
+        let mut decompressed = Vec::new();
+        MultiGzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut decompressed)
+            .with_context(|| format!("Failed to decompress file: {}", file_path))?;
+        bytes = decompressed;
+    }
+
+    decode_input(bytes).with_context(|| format!("Failed to read file: {}", file_path))
+}
+
+fn is_gzip(file_path: &str) -> bool {
+    file_path.to_ascii_lowercase().ends_with(".gz")
+}
+
+/// Find the `*.har` and `*.har.gz` files in a directory, in path order.
+fn har_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let entries = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
+
+        for entry in entries {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_default();
+
+            if path.is_dir() {
+                if recursive {
+                    dirs.push(path);
+                }
+            } else if name.ends_with(".har") || name.ends_with(".har.gz") {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Load every HAR in a directory and merge them, files that can't be read are
+/// reported to stderr and skipped.
+///
+/// Unless streaming, the raw documents are merged too, for the commands that
+/// print the document and shouldn't lose fields the model doesn't know.
+fn load_dir(dir: &Path, recursive: bool, stream: bool) -> Result<(Har, Option<json::JsonValue>)> {
+    let mut hars = Vec::new();
+    let mut docs = Vec::new();
+
+    for path in har_files(dir, recursive)? {
+        let file = path.to_string_lossy();
+        let loaded = if stream {
+            stream_har(&file).map(|har| (har, None))
+        } else {
+            read_input(&file).and_then(|contents| {
+                let contents = wrap_entries(contents);
+                let har = parse_har(&contents)?;
+                Ok((har, Some(json::parse(&contents)?)))
+            })
+        };
+
+        match loaded {
+            Ok((har, doc)) => {
+                log::info!("Loaded {} entries from {}", har.log.entries.len(), file);
+                let name = path.file_name().map_or_else(
+                    || file.to_string(),
+                    |name| name.to_string_lossy().to_string(),
+                );
+                if let Some(doc) = doc {
+                    docs.push((name.clone(), doc));
+                }
+                hars.push((name, har));
+            }
+            Err(e) => eprintln!("{}: skipping {}: {:#}", "Warning".yellow().bold(), file, e),
+        }
+    }
+
+    let merged = merge::merge(hars)
+        .ok_or_else(|| anyhow!("No HAR files could be read from the directory"))?;
+    Ok((merged, merge::merge_raw(docs)))
 }
 
-impl AsRef<str> for SortBy {
-    fn as_ref(&self) -> &str {
-        match self {
-            SortBy::Frequency => "frequency",
-            SortBy::Alpha => "alpha",
+const DEFAULT_VERIFY_CONCURRENCY: usize = 8;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Decode input as UTF-8, stripping a byte order mark and transcoding UTF-16
+/// as some tools save captures that way.
+fn decode_input(bytes: Vec<u8>) -> Result<String> {
+    let utf16 = |body: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        if !body.len().is_multiple_of(2) {
+            return Err(anyhow!("UTF-16 input has an odd number of bytes"));
+        }
+        let units: Vec<u16> = body
+            .chunks_exact(2)
+            .map(|pair| from_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).context("Input isn't valid UTF-16")
+    };
+
+    if let Some(body) = bytes.strip_prefix(UTF16_LE_BOM) {
+        log::info!("Transcoding UTF-16 LE input");
+        utf16(body, u16::from_le_bytes)
+    } else if let Some(body) = bytes.strip_prefix(UTF16_BE_BOM) {
+        log::info!("Transcoding UTF-16 BE input");
+        utf16(body, u16::from_be_bytes)
+    } else {
+        let mut text = String::from_utf8(bytes).context("Input isn't valid UTF-8")?;
+        if text.starts_with('\u{feff}') {
+            log::info!("Stripping UTF-8 byte order mark");
+            text.drain(..'\u{feff}'.len_utf8());
         }
+        Ok(text)
     }
 }
 
-#[derive(Debug, clap::Args)]
-struct SearchForArgs {
-    /// The string to search for.
-    string: String,
-}
+/// Parse a HAR straight from stdin or a file, i.e. a FIFO, without holding the
+/// raw text in memory alongside the parsed entries.
+fn stream_har(file_path: &str) -> Result<Har> {
+    let reader: Box<dyn Read> = match file_path {
+        "-" => Box::new(io::stdin().lock()),
+        _ => {
+            let file = fs::File::open(file_path)
+                .with_context(|| format!("Failed to open file: {}", file_path))?;
+            if is_gzip(file_path) {
+                Box::new(MultiGzDecoder::new(io::BufReader::new(file)))
+            } else {
+                Box::new(file)
+            }
+        }
+    };
 
-#[tokio::main]
-async fn main() -> ExitCode {
-    env_logger::init();
+    let mut reader = io::BufReader::new(reader);
 
-    if let Err(e) = run().await {
-        eprintln!("{}: {:#}", "Error".red().bold(), e);
-        return ExitCode::FAILURE;
+    // skip a UTF-8 byte order mark, UTF-16 can't be parsed as it's read
+    let start = reader.fill_buf()?;
+    if start.starts_with(UTF8_BOM) {
+        reader.consume(UTF8_BOM.len());
+    } else if start.starts_with(UTF16_LE_BOM) || start.starts_with(UTF16_BE_BOM) {
+        return Err(anyhow!(
+            "UTF-16 input can't be streamed, run without --stream to transcode it"
+        ));
     }
-    ExitCode::SUCCESS
+
+    serde_json::from_reader(reader).map_err(|e| anyhow!("Failed to parse streamed HAR: {}", e))
 }
 
-#[allow(unreachable_code)]
-fn read_input(file_path: &String) -> Result<String> {
-    fs::read_to_string(file_path).with_context(|| format!("Failed to read file: {}", file_path))
+/// Wrap a bare JSON array of entries into a minimal HAR, as some tools export.
+fn wrap_entries(contents: String) -> String {
+    if !contents.trim_start().starts_with('[') {
+        return contents;
+    }
+
+    // leave invalid input for parse_har to report
+    let Ok(entries @ json::JsonValue::Array(_)) = json::parse(&contents) else {
+        return contents;
+    };
+    log::info!(
+        "Wrapping bare array of {} entries into a HAR",
+        entries.len()
+    );
+
+    let har = json::object! {
+        log: {
+            version: "1.2",
+            creator: {
+                name: "harper",
+                version: env!("CARGO_PKG_VERSION"),
+            },
+            entries: entries,
+        }
+    };
+
+    json::stringify_pretty(har, 4)
 }
 
 fn parse_har(input: &str) -> Result<Har> {
@@ -204,132 +1236,917 @@ fn parse_har(input: &str) -> Result<Har> {
         .context("Failed to parse HAR file")
 }
 
-async fn run() -> Result<()> {
-    let args = Args::parse();
+/// Parse the raw document, keeping only the entries left in `parsed` after
+/// filtering, for the commands that print raw entries.
+fn filtered_document(contents: &str, parsed: &Har) -> Result<json::JsonValue> {
+    let mut har = json::parse(contents)?;
+    let mut entries: Vec<Option<json::JsonValue>> = har["log"]["entries"]
+        .members_mut()
+        .map(|entry| Some(entry.take()))
+        .collect();
+
+    let kept = parsed
+        .log
+        .entries
+        .iter()
+        .filter_map(|entry| entries.get_mut(entry.index)?.take())
+        .collect();
+    har["log"]["entries"] = json::JsonValue::Array(kept);
+    Ok(har)
+}
+
+/// Apply a filter, printing how many entries it kept to stderr when explaining.
+fn apply_filter<F: FnOnce(&mut Har)>(har: &mut Har, name: &str, explain: bool, filter: F) {
+    let before = har.log.entries.len();
+    filter(har);
+
+    if explain {
+        eprintln!("{}: {} -> {}", name, before, har.log.entries.len());
+    }
+}
+
+fn tld_extractor(args: &Args) -> TldExtractor {
+    TldOption::default()
+        .cache_path(args.tld_cache.as_deref().unwrap_or(".tld_cache"))
+        .private_domains(false)
+        .update_local(false)
+        .naive_mode(false)
+        .build()
+}
+
+/// Read an optional domain list, empty if no file is given.
+fn load_domain_list(path: Option<&str>) -> Result<domain_lists::DomainList> {
+    path.map_or_else(
+        || Ok(domain_lists::DomainList::default()),
+        |path| domain_lists::DomainList::load(Path::new(path)),
+    )
+}
+
+/// Log diagnostics to stderr, silent by default unless RUST_LOG is set.
+fn init_logger(level: Option<log::LevelFilter>) {
+    let mut builder = env_logger::Builder::from_default_env();
+
+    match level {
+        Some(level) => {
+            builder.filter_level(level);
+        }
+        None if std::env::var_os("RUST_LOG").is_none() => {
+            builder.filter_level(log::LevelFilter::Off);
+        }
+        None => {}
+    }
+
+    builder.init();
+}
+
+async fn run(mut args: Args) -> Result<ExitCode> {
+    let config = config::load_config(args.config.as_deref())?;
+    config::apply_config(&mut args, config);
+
+    match args.color {
+        Some(ColorChoice::Always) => colored::control::set_override(true),
+        Some(ColorChoice::Never) => colored::control::set_override(false),
+        Some(ColorChoice::Auto) | None => {}
+    }
 
     match &args.command {
-        Commands::GetBlockLists => return blocklist::download_all_blocklists().await,
-        Commands::RemoveBlockLists => return blocklist::remove_blocklists(),
+        Commands::GetBlockLists => {
+            blocklist::download_all_blocklists().await?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Commands::RemoveBlockLists => {
+            blocklist::remove_blocklists()?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Commands::Schema(schema_args) => {
+            let text = match &schema_args.command {
+                Some(command) => serde_json::to_string_pretty(&schema::schema(command)?)?,
+                None => serde_json::to_string_pretty(&schema::schemas())?,
+            };
+            writeln!(io::stdout(), "{}", text)?;
+            return Ok(ExitCode::SUCCESS);
+        }
         _ => {}
     }
 
-    let contents = match args.file {
-        stdin if stdin == "-" => {
-            let mut stdin = io::stdin();
-            if stdin.is_terminal() {
-                #[allow(unreachable_code)]
-                return Err(Args::command()
-                    .error(
-                        ErrorKind::MissingRequiredArgument,
-                        "Missing required argument: either provide a file or pipe input.",
-                    )
-                    .exit());
+    if args.file == "-" && io::stdin().is_terminal() {
+        Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "Missing required argument: either provide a file or pipe input.",
+            )
+            .exit();
+    }
+
+    // these print the original document, so need the raw text
+    if args.stream
+        && matches!(
+            args.command,
+            Commands::Output(_) | Commands::Entry(_) | Commands::Redact(_)
+        )
+    {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--stream can't be used with Output, Entry or Redact, they need the whole document.",
+            )
+            .exit();
+    }
+
+    let (contents, mut parsed) = if Path::new(&args.file).is_dir() {
+        let (parsed, doc) = load_dir(Path::new(&args.file), args.recursive, args.stream)?;
+        // the commands that print the document get the merged one
+        let contents = doc.map(json::stringify).unwrap_or_default();
+        (contents, parsed)
+    } else if args.stream {
+        (String::new(), stream_har(&args.file)?)
+    } else {
+        let contents = match args.file.as_str() {
+            "-" => {
+                let mut bytes = Vec::new();
+                io::stdin().read_to_end(&mut bytes)?;
+                decode_input(bytes)?
             }
+            _ => read_input(&args.file)?,
+        };
 
-            let mut contents = String::new();
-            stdin.read_to_string(&mut contents)?;
-            contents
-        }
-        file => read_input(&file)?,
+        let contents = wrap_entries(contents);
+        let parsed = parse_har(&contents)?;
+        (contents, parsed)
     };
 
-    let mut parsed = parse_har(&contents)?;
+    for (index, entry) in parsed.log.entries.iter_mut().enumerate() {
+        entry.index = index;
+    }
+
+    // checked before filtering so request numbers match the file
+    if args.strict {
+        let malformed = validate::strict_check(&parsed);
+        if !malformed.is_empty() {
+            let units = fmt::Units { raw: args.raw };
+            render::malformed_entries(&mut io::stderr().lock(), &malformed, units)?;
+            return Err(anyhow!(
+                "{} of {} entries are malformed",
+                malformed.len(),
+                parsed.log.entries.len()
+            ));
+        }
+    }
+
+    let explain = args.explain_filters;
+    if explain {
+        eprintln!("input: {} entries", parsed.log.entries.len());
+    }
+
+    // first so the numbers match the file, cutting the end first leaves the
+    // numbering --after-request uses in place
+    if let Some(request_num) = args.before_request {
+        apply_filter(&mut parsed, "before request filter", explain, |har| {
+            filter::filter_before_request(har, request_num)
+        });
+    }
+
+    if let Some(request_num) = args.after_request {
+        apply_filter(&mut parsed, "after request filter", explain, |har| {
+            filter::filter_after_request(har, request_num)
+        });
+    }
+
+    if let Some(time) = args.before {
+        let dt = time.resolve(args.timezone).map_err(|e| anyhow!(e))?;
+        apply_filter(&mut parsed, "before filter", explain, |har| {
+            filter::filter_by_time(har, dt, filter::TimeBound::Before)
+        });
+    }
+
+    if let Some(time) = args.after {
+        let dt = time.resolve(args.timezone).map_err(|e| anyhow!(e))?;
+        apply_filter(&mut parsed, "after filter", explain, |har| {
+            filter::filter_by_time(har, dt, filter::TimeBound::After)
+        });
+    }
+
+    if let Some(range) = args.range {
+        apply_filter(&mut parsed, "range filter", explain, |har| {
+            filter::filter_by_index(har, range)
+        });
+    }
+
+    if let Some(range) = args.size_range {
+        apply_filter(&mut parsed, "size filter", explain, |har| {
+            filter::filter_by_size(har, range)
+        });
+    }
+
+    if let Some(search_str) = &args.around_match {
+        apply_filter(&mut parsed, "around match filter", explain, |har| {
+            filter::filter_around_match(har, search_str, args.window)
+        });
+    }
+
+    if let Some(threshold) = args.slow_phase {
+        apply_filter(&mut parsed, "slow phase filter", explain, |har| {
+            filter::filter_by_phase(har, threshold)
+        });
+    }
+
+    for header in &args.has_header {
+        apply_filter(&mut parsed, "header filter", explain, |har| {
+            filter::filter_by_header(har, header)
+        });
+    }
+
+    if args.only_with_body {
+        apply_filter(&mut parsed, "body filter", explain, |har| {
+            filter::filter_by_body_presence(har, args.body_side)
+        });
+    }
+
+    if parsed.log.entries.is_empty() {
+        if !args.quiet {
+            match &args.name {
+                Some(name) => eprintln!("No entries to analyse in {} (0 after filters).", name),
+                None => eprintln!("No entries to analyse (0 after filters)."),
+            }
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let repeat = args.repeat.max(1);
+    let mut elapsed = Vec::with_capacity(repeat);
+    let mut code = ExitCode::SUCCESS;
+    for i in 0..repeat {
+        let start = Instant::now();
+
+        // only the last run's output is shown
+        if i + 1 == repeat {
+            code = dispatch(
+                &args,
+                &parsed,
+                &contents,
+                &mut io::stdout().lock(),
+                &mut io::stderr(),
+                !args.no_pager,
+            )
+            .await?;
+        } else {
+            dispatch(
+                &args,
+                &parsed,
+                &contents,
+                &mut io::sink(),
+                &mut io::sink(),
+                false,
+            )
+            .await?;
+        }
 
-    if let Some(dt) = args.before {
-        filter::filter_by_time(&mut parsed, dt, false);
+        elapsed.push(start.elapsed());
     }
 
-    if let Some(dt) = args.after {
-        filter::filter_by_time(&mut parsed, dt, true);
+    if repeat > 1 {
+        let total: Duration = elapsed.iter().sum();
+        let fastest = elapsed.iter().min().copied().unwrap_or_default();
+        let slowest = elapsed.iter().max().copied().unwrap_or_default();
+        eprintln!(
+            "{} runs: total {:?}, mean {:?}, min {:?}, max {:?}",
+            repeat,
+            total,
+            total / repeat as u32,
+            fastest,
+            slowest
+        );
     }
 
-    match args.command {
+    Ok(code)
+}
+
+/// Run the selected command on the parsed capture, writing its output to `out`
+/// and side notes such as page group summaries to `err`.
+///
+/// Checks like Budget give a failing exit code when they don't pass.
+async fn dispatch(
+    args: &Args,
+    parsed: &Har,
+    contents: &str,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+    paging: bool,
+) -> Result<ExitCode> {
+    let units = fmt::Units { raw: args.raw };
+    let json = args.json;
+    let mut code = ExitCode::SUCCESS;
+
+    match &args.command {
         Commands::CountUrls(count_args) => {
-            let tld_extractor = TldOption::default()
-                .cache_path(".tld_cache")
-                .private_domains(false)
-                .update_local(false)
-                .naive_mode(false)
-                .build();
+            let tld_extractor = tld_extractor(args);
 
             let mut domain_tree = count_urls::DomainNode::default();
             count_urls::build_domain_tree(
-                &parsed,
+                parsed,
                 &mut domain_tree,
                 &tld_extractor,
-                count_args.merge_tld,
+                count_urls::TreeOptions {
+                    merge_tld: count_args.merge_tld,
+                    merge_www: count_args.merge_www,
+                    include_data_uris: count_args.include_data_uris,
+                },
             );
 
-            match count_args.sort {
-                SortBy::Alpha => {
-                    count_urls::print_tree(&domain_tree, &mut |(name, _)| name.to_string());
-                }
-                SortBy::Frequency => {
-                    count_urls::print_tree(&domain_tree, &mut |(_, node)| Reverse(node.count));
-                }
+            if count_args.total_only {
+                render::emit(out, json, &domain_tree.total(), |out, total| {
+                    writeln!(out, "{}", total)
+                })?;
+            } else {
+                render::emit(out, json, &domain_tree, |out, tree| {
+                    render::domain_tree(out, tree, &count_args.sort, count_args.flat)
+                })?;
             }
         }
 
-        Commands::ListDomains => {
-            let domains = list_domains::list_domains(&parsed);
-            for domain in domains {
-                println!("{}", domain);
-            }
+        Commands::ListDomains(list_args) if list_args.count => {
+            let counts = list_domains::domain_counts(parsed);
+            render::emit(out, json, &counts, |out, counts| {
+                render::domain_counts(out, counts)
+            })?;
         }
 
-        Commands::CountSchemes => {
-            let mut counts = HashMap::new();
-            count_schemes::get_counts(&parsed, &mut counts);
-
-            let mut counts_vec: Vec<(&String, &usize)> = counts.iter().collect();
-            counts_vec.sort_by_key(|a| Reverse(a.1));
+        Commands::ListDomains(_) => {
+            let domains = list_domains::sorted_domains(parsed);
+            render::emit(out, json, &domains, |out, domains| {
+                render::lines(out, domains)
+            })?;
+        }
 
-            for (scheme, count) in counts_vec {
-                println!("{}: {}", scheme, count);
-            }
+        Commands::CountSchemes => {
+            let counts = count_schemes::get_counts(parsed);
+            render::emit(out, json, &counts, render::scheme_counts)?;
         }
 
         Commands::CountRequests => {
-            let count = count_requests::get_counts(&parsed);
+            let count = count_requests::get_counts(parsed);
+            render::emit(out, json, &count, render::request_count)?;
+        }
 
-            println!("Found {} requests.", count);
+        Commands::SearchFor(search_args) if search_args.invert => {
+            let missing = match &search_args.json_path {
+                Some(path) => search_for::search_json_path_missing(
+                    parsed,
+                    path,
+                    &search_args.strings,
+                    search_args.mode(),
+                ),
+                None => {
+                    search_for::search_for_missing(parsed, &search_args.strings, search_args.mode())
+                }
+            };
+            render::emit(out, json, &missing, |out, missing| {
+                render::search_missing(out, missing, search_args.format.as_ref())
+            })?;
         }
 
         Commands::SearchFor(search_args) => {
-            let matches = search_for::search_for(&parsed, &search_args.string);
-            for result in matches {
-                println!("Found in request {}:", result.request_num);
-                println!(
-                    "Time: {}\nURL: {}\nMethod: {}\nIn fields: {:?}\n",
-                    result.time, result.url, result.method, result.in_fields
-                );
+            let matches = search_args.search(parsed, &search_args.strings);
+
+            // search for each string encoded, but report the string as given
+            let b64_strings: Vec<String> = search_args
+                .strings
+                .iter()
+                .map(|string| search_args.base64_variant.encode(string))
+                .collect();
+            let mut matches_b64 = search_args.search(parsed, &b64_strings);
+            for result in &mut matches_b64 {
+                for needle in &mut result.needles {
+                    if let Some(i) = b64_strings.iter().position(|s| s == needle) {
+                        needle.clone_from(&search_args.strings[i]);
+                    }
+                }
             }
 
-            let b64_search_string = BASE64_STANDARD_NO_PAD.encode(&search_args.string);
-            let matches_b64 = search_for::search_for(&parsed, &b64_search_string);
-            for result in matches_b64 {
-                println!("Found base64 encoded in request {}:", result.request_num);
-                println!(
-                    "Time: {}\nURL: {}\nMethod: {}\nIn fields: {:?}\n",
-                    result.time, result.url, result.method, result.in_fields
+            let (matches, base64_matches) = if search_args.group_by_request {
+                // one block per request, with base64 matches marked in the field list
+                let mut grouped: BTreeMap<usize, search_for::SearchResult> = BTreeMap::new();
+                let b64_results = matches_b64.into_iter().map(|mut result| {
+                    for field in &mut result.in_fields {
+                        field.push_str(" (base64)");
+                    }
+                    result
+                });
+
+                for result in matches.into_iter().chain(b64_results) {
+                    match grouped.entry(result.request_num) {
+                        btree_map::Entry::Vacant(slot) => {
+                            slot.insert(result);
+                        }
+                        btree_map::Entry::Occupied(mut slot) => {
+                            let existing = slot.get_mut();
+                            for field in result.in_fields {
+                                if !existing.in_fields.contains(&field) {
+                                    existing.in_fields.push(field);
+                                }
+                            }
+                            for needle in result.needles {
+                                if !existing.needles.contains(&needle) {
+                                    existing.needles.push(needle);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                (grouped.into_values().collect(), Vec::new())
+            } else {
+                (matches, matches_b64)
+            };
+
+            let fuzzy_matches = if search_args.fuzzy {
+                search_args
+                    .strings
+                    .iter()
+                    .flat_map(|string| {
+                        search_for::fuzzy_hosts(parsed, string, search_args.max_distance)
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            let search = render::SearchOutput {
+                needles: search_args.strings.clone(),
+                matches,
+                base64_matches,
+                fuzzy_matches,
+            };
+            render::emit(out, json, &search, |out, search| {
+                render::search_results(out, search, search_args.format.as_ref())
+            })?;
+        }
+
+        Commands::Output(output_args) => {
+            let mut har = json::parse(contents)?;
+            if output_args.decode_bodies {
+                decode::decode_bodies(&mut har);
+            }
+            if output_args.annotate {
+                let tld_extractor = tld_extractor(args);
+                // the site comes from the document being annotated, which
+                // isn't filtered, rather than the first entry left by filters
+                let unfiltered = parse_har(contents)?;
+                let classifier = domain_lists::PartyClassifier::new(
+                    &unfiltered,
+                    &tld_extractor,
+                    domain_lists::DomainList::default(),
+                    domain_lists::DomainList::default(),
                 );
+                annotate::annotate(&mut har, &classifier);
             }
+            if output_args.group_by_page {
+                for group in pages::group_by_page(&mut har) {
+                    let page = match (&group.pageref, &group.title) {
+                        (Some(pageref), Some(title)) => format!("{} ({})", pageref, title),
+                        (Some(pageref), None) => pageref.clone(),
+                        (None, _) => "(no page)".to_string(),
+                    };
+                    writeln!(err, "# {}: {} entries", page, group.entries)?;
+                }
+            }
+            pager::page(out, &json::stringify_pretty(har, 4), paging)?;
         }
 
-        Commands::Output => {
-            println!("{}", json::stringify_pretty(json::parse(&contents)?, 4));
+        Commands::DNSSECAudit(dnssec_args) if dnssec_args.list_only => {
+            let domains = list_domains::sorted_domains(parsed);
+            render::emit(out, json, &domains, |out, domains| {
+                render::lines(out, domains)
+            })?;
         }
 
-        Commands::DNSSECAudit => dns::dnssec_audit(&parsed).await?,
+        Commands::DNSSECAudit(dnssec_args) => {
+            let results = dns::dnssec_audit(parsed).await?;
+            if dnssec_args.summary_only {
+                let summary = dns::summarise_dnssec(&results);
+                render::emit(out, json, &summary, |out, summary| {
+                    render::dnssec_summary(out, summary)
+                })?;
+            } else {
+                render::emit(out, json, &results, |out, results| {
+                    render::dnssec(out, results)
+                })?;
+            }
+        }
 
-        Commands::DNSLookup => dns::dns_lookup(&parsed).await?,
+        Commands::DNSLookup => {
+            let results = dns::dns_lookup(parsed).await?;
+            render::emit(out, json, &results, |out, results| {
+                render::dns_records(out, results)
+            })?;
+        }
 
         Commands::GetBlockLists => unreachable!(),
 
         Commands::RemoveBlockLists => unreachable!(),
 
-        Commands::BlockList => blocklist::check_blocklists(&parsed)?,
+        Commands::Schema(_) => unreachable!(),
+
+        Commands::BlockList => {
+            let results = blocklist::check_blocklists(parsed)?;
+            render::emit(out, json, &results, |out, results| {
+                render::blocklist_matches(out, results)
+            })?;
+        }
+
+        Commands::ExportPostman => {
+            let collection = export_postman::export_postman(parsed);
+            pager::page(out, &serde_json::to_string_pretty(&collection)?, paging)?;
+        }
+
+        Commands::WhereParam(param_args) => {
+            let (name, value) = match param_args.param.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (param_args.param.as_str(), None),
+            };
+
+            let matches = where_param::where_param(parsed, name, value);
+            render::emit(out, json, &matches, |out, matches| {
+                render::param_matches(out, matches)
+            })?;
+        }
+
+        Commands::Bloat(bloat_args) => {
+            let results = bloat::find_bloat(parsed, bloat_args.threshold);
+            render::emit(out, json, &results, |out, results| {
+                render::bloat(out, results, units)
+            })?;
+        }
+
+        Commands::Connections => {
+            let stats = connections::get_connection_stats(parsed);
+            render::emit(out, json, &stats, render::connection_stats)?;
+        }
+
+        Commands::PrivateIps => {
+            let results = private_ips::find_private_ips(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::private_ips(out, results)
+            })?;
+        }
+
+        Commands::HostTimings => {
+            let results = host_timings::host_timings(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::host_timings(out, results, units)
+            })?;
+        }
+
+        Commands::MimeMismatch => {
+            let results = mime_mismatch::find_mismatches(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::mime_mismatches(out, results)
+            })?;
+        }
+
+        Commands::FindJson(find_args) => {
+            let matches = find_json::find_json(parsed, &find_args.key);
+            render::emit(out, json, &matches, |out, matches| {
+                render::json_matches(out, matches)
+            })?;
+        }
+
+        Commands::CookieFlow => {
+            let flows = cookie_flow::cookie_flow(parsed);
+            render::emit(out, json, &flows, render::cookie_flows)?;
+        }
+
+        Commands::Urls(urls_args) => {
+            let urls = urls::unique_urls(parsed, urls_args.strip_query);
+            render::emit(out, json, &urls, |out, urls| {
+                render::urls(out, urls, urls_args.count)
+            })?;
+        }
+
+        Commands::CookieAudit(cookie_args) => {
+            let findings = cookie_audit::cookie_audit(parsed, cookie_args.max_age_days);
+            render::emit(out, json, &findings, |out, findings| {
+                render::cookie_findings(out, findings)
+            })?;
+        }
+
+        Commands::Entry(entry_args) => {
+            let mut har = filtered_document(contents, parsed)?;
+            let entries = &mut har["log"]["entries"];
+            let len = entries.len();
+
+            // request numbers are 1-based, -1 is the last entry, both counted
+            // after filtering like every other command
+            let index = body::resolve_index(entry_args.index, len)?;
+
+            pager::page(
+                out,
+                &json::stringify_pretty(entries[index].take(), 4),
+                paging,
+            )?;
+        }
+
+        Commands::Redirects(redirect_args) => {
+            let chains = redirects::redirect_chains(parsed);
+
+            let verifications = if redirect_args.verify {
+                Some(
+                    redirects::verify_chains(
+                        &chains,
+                        Duration::from_secs(redirect_args.timeout),
+                        redirect_args
+                            .concurrency
+                            .unwrap_or(DEFAULT_VERIFY_CONCURRENCY),
+                    )
+                    .await?,
+                )
+            } else {
+                None
+            };
+
+            let output = render::RedirectsOutput {
+                chains,
+                verifications,
+            };
+            render::emit(out, json, &output, |out, output| {
+                render::redirects(out, output, redirect_args.list)
+            })?;
+        }
+
+        Commands::Compression => {
+            let results = compression::compression_by_mime(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::compression(out, results, units)
+            })?;
+        }
+
+        Commands::SoftErrors(soft_args) => {
+            let markers = if soft_args.markers.is_empty() {
+                soft_errors::DEFAULT_MARKERS.map(String::from).to_vec()
+            } else {
+                soft_args.markers.clone()
+            };
+
+            let results = soft_errors::find_soft_errors(parsed, &markers);
+            render::emit(out, json, &results, |out, results| {
+                render::soft_errors(out, results)
+            })?;
+        }
+
+        Commands::DevtoolsSummary => {
+            let summary = devtools_summary::devtools_summary(parsed);
+            render::emit(out, json, &summary, |out, summary| {
+                render::devtools_summary(out, summary, units)
+            })?;
+        }
+
+        Commands::Rate(rate_args) => {
+            let series = rate::request_rate(parsed, rate_args.interval);
+            render::emit(out, json, &series, |out, series| {
+                render::rate(out, series, rate_args.sparkline)
+            })?;
+        }
+
+        Commands::Chunked => {
+            let results = chunked::find_chunked(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::chunked(out, results)
+            })?;
+        }
+
+        Commands::Graph(graph_args) => {
+            write!(out, "{}", graph::graph(parsed, graph_args.size_by))?;
+        }
+
+        Commands::CookieStats => {
+            let stats = cookie_stats::cookie_stats(parsed);
+            render::emit(out, json, &stats, |out, stats| {
+                render::cookie_stats(out, stats, units)
+            })?;
+        }
+
+        Commands::CacheControl => {
+            let stats = cache_control::cache_control(parsed);
+            render::emit(out, json, &stats, render::cache_control)?;
+        }
+
+        Commands::Ports => {
+            let usages = ports::non_standard_ports(parsed);
+            render::emit(out, json, &usages, |out, usages| render::ports(out, usages))?;
+        }
+
+        Commands::DuplicateHeaders => {
+            let results = duplicate_headers::duplicate_headers(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::duplicate_headers(out, results)
+            })?;
+        }
+
+        Commands::Budget(budget_args) => {
+            let budget = budget_args.budget_kb * 1024;
+            let checks = if budget_args.per_page {
+                budget::check_pages(parsed, budget)
+            } else {
+                vec![budget::check_total(parsed, budget)]
+            };
+
+            if !checks.iter().all(|check| check.passed()) {
+                code = ExitCode::FAILURE;
+            }
+            render::emit(out, json, &checks, |out, checks| {
+                render::budget(out, checks, budget, units)
+            })?;
+        }
+
+        Commands::Comments => {
+            let comments = comments::comments(parsed);
+            render::emit(out, json, &comments, |out, comments| {
+                render::comments(out, comments)
+            })?;
+        }
+
+        Commands::TimingCheck => {
+            let issues = validate::timing_check(parsed);
+            render::emit(out, json, &issues, |out, issues| {
+                render::timing_issues(out, issues, units)
+            })?;
+        }
+
+        Commands::HostMimes => {
+            let results = host_mimes::host_mimes(parsed);
+            render::emit(out, json, &results, |out, results| {
+                render::host_mimes(out, results)
+            })?;
+        }
+
+        Commands::HttpsAudit => {
+            let requests = https_audit::https_audit(parsed);
+            render::emit(out, json, &requests, |out, requests| {
+                render::insecure_requests(out, requests)
+            })?;
+        }
+
+        Commands::Body(body_args) => {
+            let side = if body_args.request {
+                body::BodySide::Request
+            } else {
+                body::BodySide::Response
+            };
+
+            match body::body(parsed, body_args.index, side)? {
+                body::Body::Text(text) => pager::page(out, &text, paging)?,
+                body::Body::Binary(bytes) if args.raw => out.write_all(&bytes)?,
+                body::Body::Binary(bytes) => pager::page(out, &body::hex_dump(&bytes), paging)?,
+            }
+        }
+
+        Commands::LongQuery(query_args) => {
+            let results =
+                long_query::find_long_queries(parsed, query_args.threshold, query_args.max_params);
+            render::emit(out, json, &results, |out, results| {
+                render::long_queries(out, results, units)
+            })?;
+        }
+
+        Commands::Redact(redact_args) => {
+            // entries dropped by the filters aren't meant to be shared
+            let mut har = filtered_document(contents, parsed)?;
+            redact::redact(&mut har, redact_args.keep_bodies);
+            pager::page(out, &json::stringify_pretty(har, 4), paging)?;
+        }
+
+        Commands::Cors => {
+            let pairs = cors::cors_pairs(parsed);
+            render::emit(out, json, &pairs, |out, pairs| {
+                render::cors_pairs(out, pairs)
+            })?;
+        }
+
+        Commands::NoCache => {
+            let assets = no_cache::uncached_assets(parsed);
+            render::emit(out, json, &assets, |out, assets| {
+                render::uncached_assets(out, assets)
+            })?;
+        }
+
+        Commands::UserAgents => {
+            let counts = user_agents::user_agents(parsed);
+            render::emit(out, json, &counts, render::user_agents)?;
+        }
+
+        Commands::Retries => {
+            let groups = retries::find_retries(parsed);
+            render::emit(out, json, &groups, |out, groups| {
+                render::retries(out, groups, units)
+            })?;
+        }
+
+        Commands::Serialization(serialization_args) => {
+            let serialization = serialization::find_serialization(
+                parsed,
+                serialization_args.max_gap,
+                serialization_args.min_savings,
+            );
+            render::emit(out, json, &serialization, |out, serialization| {
+                render::serialization(out, serialization, units)
+            })?;
+        }
+
+        Commands::ThirdParty(party_args) => {
+            let tld_extractor = tld_extractor(args);
+            let classifier = domain_lists::PartyClassifier::new(
+                parsed,
+                &tld_extractor,
+                load_domain_list(party_args.allowlist.as_deref())?,
+                load_domain_list(party_args.blocklist.as_deref())?,
+            );
+
+            let domains = third_party::third_parties(parsed, &classifier);
+            render::emit(out, json, &domains, |out, domains| {
+                render::third_parties(out, domains, classifier.site(), units)
+            })?;
+        }
+
+        Commands::Gzippable(gzippable_args) => {
+            let results = gzippable::find_gzippable(parsed, gzippable_args.min_size);
+            render::emit(out, json, &results, |out, results| {
+                render::gzippable(out, results, units)
+            })?;
+        }
+
+        Commands::SizeRatio(ratio_args) => {
+            let ratios =
+                size_ratio::size_ratios(parsed, ratio_args.threshold, ratio_args.min_bytes);
+            render::emit(out, json, &ratios, |out, ratios| {
+                render::size_ratios(out, ratios, units)
+            })?;
+        }
+
+        Commands::CriticalPath => {
+            let paths = critical_path::critical_paths(parsed);
+            render::emit(out, json, &paths, |out, paths| {
+                render::critical_paths(out, paths, units)
+            })?;
+        }
+
+        Commands::DnsTimings(dns_args) => {
+            let mut results = dns_timings::dns_timings(parsed, dns_args.slow);
+            if dns_args.slow_only {
+                results.retain(|result| result.slow);
+            }
+            render::emit(out, json, &results, |out, results| {
+                render::dns_timings(out, results, units)
+            })?;
+        }
+
+        Commands::Initiators(initiator_args) => {
+            let initiators = initiators::initiators(parsed, initiator_args.top);
+            render::emit(out, json, &initiators, render::initiators)?;
+        }
+
+        Commands::Hashes(hashes_args) => {
+            let hashes = hashes::body_hashes(parsed);
+            if hashes_args.all {
+                render::emit(out, json, &hashes, |out, hashes| {
+                    render::body_hashes(out, hashes, units)
+                })?;
+            } else {
+                let duplicates = hashes::duplicates(&hashes);
+                render::emit(out, json, &duplicates, |out, duplicates| {
+                    render::duplicate_bodies(out, duplicates, units)
+                })?;
+            }
+        }
+
+        Commands::Bottleneck(bottleneck_args) => {
+            let bottlenecks = bottleneck::bottlenecks(parsed);
+            render::emit(out, json, &bottlenecks, |out, bottlenecks| {
+                render::bottlenecks(out, bottlenecks, bottleneck_args.requests, units)
+            })?;
+        }
+
+        Commands::UrlCheck => {
+            let malformed = url_check::check_urls(parsed);
+            render::emit(out, json, &malformed, |out, malformed| {
+                render::malformed_urls(out, malformed)
+            })?;
+        }
+
+        Commands::RateLimit(limit_args) => {
+            let limits = rate_limit::rate_limits(parsed, limit_args.period);
+            render::emit(out, json, &limits, |out, limits| {
+                render::rate_limits(out, limits)
+            })?;
+        }
+
+        Commands::Ttfb(ttfb_args) => {
+            let mut results = ttfb::ttfb(parsed);
+            results.truncate(ttfb_args.top);
+            render::emit(out, json, &results, |out, results| {
+                render::ttfb(out, results, units)
+            })?;
+        }
     }
 
-    Ok(())
+    Ok(code)
 }
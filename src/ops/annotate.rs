@@ -0,0 +1,53 @@
+use json::{object, JsonValue};
+
+use super::{decode, domain_lists::PartyClassifier, timing::Phase};
+
+/// Key of the object added to each entry, custom fields in a HAR start with `_`.
+pub const ANNOTATION_KEY: &str = "_harper";
+
+/// Add a `_harper` object to each entry of a raw HAR with metadata computed by
+/// harper, leaving the original fields as they are. Values that can't be worked
+/// out are `null`.
+///
+/// * `party`: `first`, `third` or `tracker`.
+/// * `registrable_domain`: i.e. `example.co.uk`.
+/// * `body_length`: bytes of the response body once decoded.
+/// * `timing_sum`: milliseconds of the phases that make up the entry's `time`.
+pub fn annotate(har: &mut JsonValue, classifier: &PartyClassifier) {
+    for entry in har["log"]["entries"].members_mut() {
+        let (domain, party) = match entry["request"]["url"]
+            .as_str()
+            .and_then(|url| classifier.classify_url(url))
+        {
+            Some((domain, party)) => (domain.into(), party.name().into()),
+            None => (JsonValue::Null, JsonValue::Null),
+        };
+
+        entry[ANNOTATION_KEY] = object! {
+            party: party,
+            registrable_domain: domain,
+            body_length: body_length(&entry["response"]["content"]),
+            timing_sum: timing_sum(&entry["timings"]),
+        };
+    }
+}
+
+fn body_length(content: &JsonValue) -> Option<usize> {
+    let text = content["text"].as_str()?;
+
+    if content["encoding"] == "base64" {
+        decode::decode_base64(text).map(|bytes| bytes.len())
+    } else {
+        Some(text.len())
+    }
+}
+
+fn timing_sum(timings: &JsonValue) -> Option<f64> {
+    let values: Vec<f64> = Phase::SUMMED
+        .iter()
+        .filter_map(|phase| timings[phase.name()].as_f64())
+        .filter(|&value| value >= 0.0)
+        .collect();
+
+    (!values.is_empty()).then(|| values.iter().sum())
+}
@@ -0,0 +1,57 @@
+use crate::har::{Har, Request};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::bytes;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct BloatResult {
+    pub request_num: usize,
+    pub url: String,
+    pub header_size: u64,
+    pub cookie_size: u64,
+}
+
+pub fn find_bloat(har: &Har, threshold: u64) -> Vec<BloatResult> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let request = &entry.request;
+            let header_size = bytes::request_headers_size(request);
+            let cookie_size = cookie_size(request);
+
+            if header_size > threshold || cookie_size > threshold {
+                Some(BloatResult {
+                    request_num: i + 1,
+                    url: request.url.clone(),
+                    header_size,
+                    cookie_size,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn cookie_size(request: &Request) -> u64 {
+    // fall back to the raw header when the cookie array is empty
+    if request.cookies.is_empty() {
+        return request
+            .headers
+            .iter()
+            .filter(|h| h.name.eq_ignore_ascii_case("cookie"))
+            .map(|h| h.value.len() as u64)
+            .sum();
+    }
+
+    // "name=value" joined with "; "
+    let pairs: u64 = request
+        .cookies
+        .iter()
+        .map(|c| (c.name.len() + c.value.len() + 1) as u64)
+        .sum();
+    pairs + 2 * (request.cookies.len() as u64 - 1)
+}
@@ -3,6 +3,8 @@ use colored::Colorize;
 use directories::ProjectDirs;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use reqwest::Client;
+use schemars::JsonSchema;
+use serde::Serialize;
 use std::{
     collections::HashSet,
     fs,
@@ -150,9 +152,17 @@ pub fn remove_blocklists() -> Result<()> {
     Ok(())
 }
 
-pub fn check_blocklists(har: &Har) -> Result<()> {
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct BlocklistMatches {
+    pub blocklist: String,
+    /// Domains in the capture found on the blocklist.
+    pub domains: Vec<String>,
+}
+
+pub fn check_blocklists(har: &Har) -> Result<Vec<BlocklistMatches>> {
     let domains = list_domains::list_domains(har);
     let blocklists_dir = get_blocklists_dir()?;
+    let mut results = Vec::new();
 
     for (_, filename) in BLOCKLISTS.iter() {
         let mut blocklist_domains = HashSet::new();
@@ -176,7 +186,7 @@ pub fn check_blocklists(har: &Har) -> Result<()> {
             blocklist_domains.insert(line);
         }
 
-        println!("{}: {}", "Checking blocklist".blue().bold(), filename);
+        let mut found_domains = Vec::new();
         for domain in &domains {
             let domain_lower = domain.to_lowercase();
             let parts: Vec<&str> = domain_lower.split('.').collect();
@@ -191,11 +201,15 @@ pub fn check_blocklists(har: &Har) -> Result<()> {
             }
 
             if found {
-                println!("{}: {}", "Found".yellow(), domain.red())
+                found_domains.push(domain.clone());
             }
         }
-        println!();
+
+        results.push(BlocklistMatches {
+            blocklist: filename.to_string(),
+            domains: found_domains,
+        });
     }
 
-    Ok(())
+    Ok(results)
 }
@@ -0,0 +1,107 @@
+use crate::Har;
+use anyhow::{anyhow, Result};
+use std::fmt::Write;
+
+use super::decode;
+
+/// Bytes shown on each line of a hex dump.
+const HEX_DUMP_WIDTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BodySide {
+    /// The request's post data.
+    Request,
+
+    /// The response content.
+    Response,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Body {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Get the 0-based index of a request number, negative numbers count from the end.
+pub fn resolve_index(index: i64, len: usize) -> Result<usize> {
+    match index {
+        i if i > 0 && i as usize <= len => Ok(i as usize - 1),
+        i if i < 0 && i.unsigned_abs() as usize <= len => Ok(len - i.unsigned_abs() as usize),
+        i => Err(anyhow!(
+            "Entry {} is out of range, the HAR has {} entries",
+            i,
+            len
+        )),
+    }
+}
+
+/// Get the decoded request or response body of an entry.
+pub fn body(har: &Har, index: i64, side: BodySide) -> Result<Body> {
+    let entries = &har.log.entries;
+    let entry = &entries[resolve_index(index, entries.len())?];
+
+    let bytes = match side {
+        BodySide::Request => entry
+            .request
+            .post_data
+            .as_ref()
+            .map(|post_data| post_data.text.as_bytes().to_vec()),
+        BodySide::Response => entry
+            .response
+            .content
+            .as_ref()
+            .and_then(decode::decode_body),
+    };
+
+    let Some(bytes) = bytes.filter(|bytes| !bytes.is_empty()) else {
+        let side = match side {
+            BodySide::Request => "request",
+            BodySide::Response => "response",
+        };
+        return Err(anyhow!("Entry {} has no {} body", index, side));
+    };
+
+    Ok(match String::from_utf8(bytes) {
+        Ok(text) => Body::Text(text),
+        Err(e) => Body::Binary(e.into_bytes()),
+    })
+}
+
+/// Format bytes as offset, hex and printable ASCII columns, like `hexdump -C`.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+
+    for (i, chunk) in bytes.chunks(HEX_DUMP_WIDTH).enumerate() {
+        if i > 0 {
+            dump.push('\n');
+        }
+        let _ = write!(dump, "{:08x}  ", i * HEX_DUMP_WIDTH);
+
+        for j in 0..HEX_DUMP_WIDTH {
+            match chunk.get(j) {
+                Some(byte) => {
+                    let _ = write!(dump, "{:02x} ", byte);
+                }
+                None => dump.push_str("   "),
+            }
+            // split into two groups of eight
+            if j == HEX_DUMP_WIDTH / 2 - 1 {
+                dump.push(' ');
+            }
+        }
+
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = write!(dump, " |{}|", ascii);
+    }
+
+    dump
+}
@@ -0,0 +1,89 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::timing::Phase;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct RequestBottleneck {
+    pub request_num: usize,
+    pub url: String,
+    /// The phase taking the most time, ssl counts as part of connect.
+    pub phase: Phase,
+    pub phase_ms: f64,
+    pub time_ms: f64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PhaseCount {
+    pub phase: Phase,
+    /// Requests the phase took the most time of.
+    pub requests: usize,
+    /// Milliseconds spent in the phase by those requests.
+    pub total_ms: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct Bottlenecks {
+    pub requests: Vec<RequestBottleneck>,
+    /// Phases by how many requests they dominated, most first.
+    pub phases: Vec<PhaseCount>,
+}
+
+/// Find the phase each request spent the most time in, and how many requests
+/// each phase dominated. Phases recording `-1` are skipped, as are requests
+/// without timings or with every phase at zero. Ties go to the earlier phase.
+pub fn bottlenecks(har: &Har) -> Bottlenecks {
+    let requests: Vec<RequestBottleneck> = har
+        .log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let timing = entry.timings.as_ref()?;
+            let (phase, phase_ms) = Phase::SUMMED
+                .iter()
+                .filter_map(|&phase| Some((phase, phase.value(timing)?)))
+                .fold(
+                    None,
+                    |slowest: Option<(Phase, f64)>, (phase, ms)| match slowest {
+                        Some((_, slowest_ms)) if slowest_ms >= ms => slowest,
+                        _ => Some((phase, ms)),
+                    },
+                )
+                .filter(|&(_, ms)| ms > 0.0)?;
+
+            Some(RequestBottleneck {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                phase,
+                phase_ms,
+                time_ms: entry.time,
+            })
+        })
+        .collect();
+
+    let mut counts: HashMap<Phase, (usize, f64)> = HashMap::new();
+    for request in &requests {
+        let (count, total) = counts.entry(request.phase).or_default();
+        *count += 1;
+        *total += request.phase_ms;
+    }
+
+    let mut phases: Vec<PhaseCount> = counts
+        .into_iter()
+        .map(|(phase, (requests, total_ms))| PhaseCount {
+            phase,
+            requests,
+            total_ms,
+        })
+        .collect();
+    phases.sort_by(|a, b| {
+        b.requests
+            .cmp(&a.requests)
+            .then_with(|| b.total_ms.total_cmp(&a.total_ms))
+    });
+
+    Bottlenecks { requests, phases }
+}
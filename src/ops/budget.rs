@@ -0,0 +1,54 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::bytes;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct BudgetCheck {
+    /// What was checked, `capture` or a page id.
+    pub name: String,
+    pub transferred: u64,
+    /// Bytes over the budget, `None` if within it.
+    pub overage: Option<u64>,
+}
+
+impl BudgetCheck {
+    fn new(name: String, transferred: u64, budget: u64) -> Self {
+        BudgetCheck {
+            name,
+            transferred,
+            overage: transferred.checked_sub(budget).filter(|&over| over > 0),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.overage.is_none()
+    }
+}
+
+/// Check the bytes transferred by the whole capture against a budget.
+pub fn check_total(har: &Har, budget: u64) -> BudgetCheck {
+    let (transferred, _) = bytes::total_bytes(har);
+    BudgetCheck::new("capture".to_string(), transferred, budget)
+}
+
+/// Check the bytes transferred by each page against a budget.
+///
+/// Entries without a page aren't counted towards any page.
+pub fn check_pages(har: &Har, budget: u64) -> Vec<BudgetCheck> {
+    har.log
+        .pages
+        .iter()
+        .flatten()
+        .map(|page| {
+            let entries = har
+                .log
+                .entries
+                .iter()
+                .filter(|entry| entry.pageref.as_deref() == Some(page.id.as_str()));
+            let (transferred, _) = bytes::sum_bytes(entries);
+            BudgetCheck::new(page.id.clone(), transferred, budget)
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+use crate::har::{Entry, Har, Header, Request, Response};
+use url::Url;
+
+/// Bytes of the response transferred over the wire, headers included.
+///
+/// `None` if the capture doesn't record the body size.
+pub fn transferred_bytes(entry: &Entry) -> Option<u64> {
+    let response = &entry.response;
+    let body = u64::try_from(response.body_size).ok()?;
+
+    Some(body + response_headers_size(response))
+}
+
+/// Bytes of the request line and headers, computed from the headers when the
+/// capture doesn't record `headersSize`.
+pub fn request_headers_size(request: &Request) -> u64 {
+    if let Some(size) = recorded_size(request.headers_size) {
+        return size;
+    }
+
+    // the request line has the path rather than the whole URL
+    let target = Url::parse(&request.url)
+        .map(|url| match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        })
+        .unwrap_or_else(|_| request.url.clone());
+    let request_line = format!("{} {} {}", request.method, target, request.http_version);
+
+    serialised_size(&request_line, &request.headers)
+}
+
+/// Bytes of the status line and headers, computed from the headers when the
+/// capture doesn't record `headersSize`.
+pub fn response_headers_size(response: &Response) -> u64 {
+    if let Some(size) = recorded_size(response.headers_size) {
+        return size;
+    }
+
+    let status_line = format!(
+        "{} {} {}",
+        response.http_version, response.status, response.status_text
+    );
+
+    serialised_size(&status_line, &response.headers)
+}
+
+fn recorded_size(headers_size: Option<i64>) -> Option<u64> {
+    headers_size.and_then(|size| u64::try_from(size).ok())
+}
+
+/// Size of a start line and headers as sent over HTTP/1.1, each line ends in
+/// CRLF with a blank line after the headers.
+fn serialised_size(start_line: &str, headers: &[Header]) -> u64 {
+    let headers: u64 = headers
+        .iter()
+        .map(|h| (h.name.len() + ": ".len() + h.value.len() + 2) as u64)
+        .sum();
+
+    start_line.len() as u64 + 2 + headers + 2
+}
+
+/// Bytes of the response body after decoding.
+///
+/// `None` if the capture doesn't record the content size.
+pub fn decoded_bytes(entry: &Entry) -> Option<u64> {
+    let size = entry.response.content.as_ref()?.size?;
+    u64::try_from(size).ok()
+}
+
+/// Total transferred and decoded bytes of entries, skipping unknown sizes.
+pub fn sum_bytes<'a, I>(entries: I) -> (u64, u64)
+where
+    I: IntoIterator<Item = &'a Entry>,
+{
+    entries
+        .into_iter()
+        .fold((0, 0), |(transferred, decoded), entry| {
+            (
+                transferred + transferred_bytes(entry).unwrap_or(0),
+                decoded + decoded_bytes(entry).unwrap_or(0),
+            )
+        })
+}
+
+/// Total transferred and decoded bytes of the whole capture.
+pub fn total_bytes(har: &Har) -> (u64, u64) {
+    sum_bytes(&har.log.entries)
+}
@@ -0,0 +1,148 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Directive {
+    /// Lowercased directive name, i.e. `max-age`.
+    pub name: String,
+    pub value: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct CacheControlStats {
+    /// Responses carrying each directive.
+    pub directives: BTreeMap<String, usize>,
+    /// Responses with each `max-age` in seconds.
+    pub max_ages: BTreeMap<u64, usize>,
+    pub with_header: usize,
+    pub without_header: usize,
+}
+
+/// Split a `Cache-Control` value into its directives.
+///
+/// Quoted values may contain commas, i.e. `no-cache="Set-Cookie, Vary"`.
+pub fn parse_directives(header: &str) -> Vec<Directive> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in header.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.push(c);
+            }
+            ',' if !quoted => items.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    items.push(current);
+
+    items
+        .iter()
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (name, value) = match item.split_once('=') {
+                Some((name, value)) => (name, Some(value.trim().trim_matches('"').to_string())),
+                None => (item, None),
+            };
+            Directive {
+                name: name.trim().to_ascii_lowercase(),
+                value,
+            }
+        })
+        .collect()
+}
+
+pub fn cache_control(har: &Har) -> CacheControlStats {
+    let mut stats = CacheControlStats::default();
+
+    for entry in &har.log.entries {
+        // repeated headers combine as though they were comma separated
+        let values: Vec<&str> = entry
+            .response
+            .headers
+            .iter()
+            .filter(|h| h.name.eq_ignore_ascii_case("cache-control"))
+            .map(|h| h.value.as_str())
+            .collect();
+
+        if values.is_empty() {
+            stats.without_header += 1;
+            continue;
+        }
+        stats.with_header += 1;
+
+        // count each directive once per response
+        let mut directives = parse_directives(&values.join(","));
+        let mut seen = HashSet::new();
+        directives.retain(|d| seen.insert(d.name.clone()));
+
+        for directive in directives {
+            if directive.name == "max-age" {
+                if let Some(ttl) = directive.value.as_deref().and_then(|v| v.parse().ok()) {
+                    *stats.max_ages.entry(ttl).or_insert(0) += 1;
+                }
+            }
+            *stats.directives.entry(directive.name).or_insert(0) += 1;
+        }
+    }
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directive(name: &str, value: Option<&str>) -> Directive {
+        Directive {
+            name: name.to_string(),
+            value: value.map(String::from),
+        }
+    }
+
+    #[test]
+    fn parses_multiple_directives() {
+        assert_eq!(
+            parse_directives("public, max-age=3600, must-revalidate"),
+            vec![
+                directive("public", None),
+                directive("max-age", Some("3600")),
+                directive("must-revalidate", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn lowercases_names_and_trims_whitespace() {
+        assert_eq!(
+            parse_directives("  No-Store ,MAX-AGE = 0 "),
+            vec![directive("no-store", None), directive("max-age", Some("0"))]
+        );
+    }
+
+    #[test]
+    fn keeps_commas_in_quoted_values() {
+        assert_eq!(
+            parse_directives("private, no-cache=\"Set-Cookie, Vary\", s-maxage=60"),
+            vec![
+                directive("private", None),
+                directive("no-cache", Some("Set-Cookie, Vary")),
+                directive("s-maxage", Some("60")),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_empty_directives() {
+        assert_eq!(
+            parse_directives("no-cache,, ,"),
+            vec![directive("no-cache", None)]
+        );
+        assert!(parse_directives("").is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamingReason {
+    /// Sent with `Transfer-Encoding: chunked`.
+    Chunked,
+    /// Sent without a `Content-Length` header.
+    NoContentLength,
+}
+
+impl fmt::Display for StreamingReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StreamingReason::Chunked => "chunked transfer encoding",
+            StreamingReason::NoContentLength => "no content-length",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ChunkedResult {
+    pub request_num: usize,
+    pub url: String,
+    pub status: u16,
+    pub reason: StreamingReason,
+}
+
+pub fn find_chunked(har: &Har) -> Vec<ChunkedResult> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let response = &entry.response;
+            let header = |name: &str| {
+                response
+                    .headers
+                    .iter()
+                    .find(|h| h.name.eq_ignore_ascii_case(name))
+                    .map(|h| h.value.to_ascii_lowercase())
+            };
+
+            let reason = if header("transfer-encoding").is_some_and(|v| v.contains("chunked")) {
+                StreamingReason::Chunked
+            } else if header("content-length").is_none() && has_body(response.status) {
+                StreamingReason::NoContentLength
+            } else {
+                return None;
+            };
+
+            Some(ChunkedResult {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                status: response.status,
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Responses that never have a body don't need a length.
+fn has_body(status: u16) -> bool {
+    !(status < 200 || status == 204 || status == 304)
+}
@@ -0,0 +1,107 @@
+use crate::har::{Cookie, Har, Header};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Comment {
+    /// Where the comment was found, i.e. `log`, `page page_1` or `response header Server`.
+    pub location: String,
+    /// Request the comment belongs to, `None` for log and page comments.
+    pub request_num: Option<usize>,
+    pub text: String,
+}
+
+/// Collect the non-empty `comment` fields throughout the capture.
+pub fn comments(har: &Har) -> Vec<Comment> {
+    let mut found = Vec::new();
+    let log = &har.log;
+
+    let mut add = |location: String, request_num: Option<usize>, comment: &Option<String>| {
+        if let Some(text) = comment.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+            found.push(Comment {
+                location,
+                request_num,
+                text: text.to_string(),
+            });
+        }
+    };
+
+    add("log".to_string(), None, &log.comment);
+    add("creator".to_string(), None, &log.creator.comment);
+    if let Some(browser) = &log.browser {
+        add("browser".to_string(), None, &browser.comment);
+    }
+
+    for page in log.pages.iter().flatten() {
+        add(format!("page {}", page.id), None, &page.comment);
+        add(
+            format!("page {} timings", page.id),
+            None,
+            &page.page_timings.comment,
+        );
+    }
+
+    for (i, entry) in log.entries.iter().enumerate() {
+        let num = Some(i + 1);
+        let request = &entry.request;
+        let response = &entry.response;
+
+        add("entry".to_string(), num, &entry.comment);
+
+        add("request".to_string(), num, &request.comment);
+        headers_and_cookies("request", &request.headers, &request.cookies, num, &mut add);
+        for query in &request.query_string {
+            add(format!("request query {}", query.name), num, &query.comment);
+        }
+        if let Some(post_data) = &request.post_data {
+            add("request postData".to_string(), num, &post_data.comment);
+            for param in post_data.params.iter().flatten() {
+                add(format!("request param {}", param.name), num, &param.comment);
+            }
+        }
+
+        add("response".to_string(), num, &response.comment);
+        headers_and_cookies(
+            "response",
+            &response.headers,
+            &response.cookies,
+            num,
+            &mut add,
+        );
+        if let Some(content) = &response.content {
+            add("response content".to_string(), num, &content.comment);
+        }
+
+        add("cache".to_string(), num, &entry.cache.comment);
+        if let Some(timings) = &entry.timings {
+            add("timings".to_string(), num, &timings.comment);
+        }
+    }
+
+    found
+}
+
+fn headers_and_cookies<F>(
+    side: &str,
+    headers: &[Header],
+    cookies: &[Cookie],
+    request_num: Option<usize>,
+    add: &mut F,
+) where
+    F: FnMut(String, Option<usize>, &Option<String>),
+{
+    for header in headers {
+        add(
+            format!("{} header {}", side, header.name),
+            request_num,
+            &header.comment,
+        );
+    }
+    for cookie in cookies {
+        add(
+            format!("{} cookie {}", side, cookie.name),
+            request_num,
+            &cookie.comment,
+        );
+    }
+}
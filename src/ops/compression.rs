@@ -0,0 +1,67 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::{bytes, mime};
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct MimeCompression {
+    /// Total bytes transferred over the wire, from `bodySize`.
+    pub transferred: u64,
+    /// Total bytes of response headers, excluded from the ratio.
+    pub headers: u64,
+    /// Total bytes after decoding, from `content.size`.
+    pub decoded: u64,
+    /// Number of entries with known sizes.
+    pub entries: usize,
+    /// Number of entries with unknown sizes, excluded from the totals.
+    pub unknown: usize,
+    pub textual: bool,
+}
+
+impl MimeCompression {
+    /// Ratio of transferred to decoded bytes, lower is better.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.decoded > 0).then(|| self.transferred as f64 / self.decoded as f64)
+    }
+
+    /// Textual types that were sent at least as large as they decode to.
+    pub fn uncompressed(&self) -> bool {
+        self.textual && self.ratio().is_some_and(|ratio| ratio >= 1.0)
+    }
+}
+
+pub fn compression_by_mime(har: &Har) -> BTreeMap<String, MimeCompression> {
+    let mut results: BTreeMap<String, MimeCompression> = BTreeMap::new();
+
+    for entry in &har.log.entries {
+        let response = &entry.response;
+        let content = response.content.as_ref();
+
+        let mime_type = content
+            .and_then(|c| c.mime_type.as_deref())
+            .map(mime::normalise)
+            .filter(|m| !m.is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let result = results.entry(mime_type.clone()).or_default();
+        result.textual = mime::is_textual(&mime_type);
+
+        let decoded = content.and_then(|c| c.size).unwrap_or(-1);
+        let transferred = response.body_size;
+
+        // nothing transferred for a non-empty body means it came from cache
+        if decoded < 0 || transferred < 0 || (transferred == 0 && decoded > 0) {
+            result.unknown += 1;
+            continue;
+        }
+
+        result.transferred += transferred as u64;
+        result.headers += bytes::response_headers_size(response);
+        result.decoded += decoded as u64;
+        result.entries += 1;
+    }
+
+    results
+}
@@ -0,0 +1,37 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct ConnectionStats {
+    /// Number of requests sent over each connection id.
+    pub connections: BTreeMap<String, usize>,
+    /// Number of requests that don't have a connection id.
+    pub unknown: usize,
+}
+
+impl ConnectionStats {
+    /// Number of distinct connections opened.
+    pub fn opened(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Number of requests that reused an already opened connection.
+    pub fn reused(&self) -> usize {
+        self.connections.values().map(|count| count - 1).sum()
+    }
+}
+
+pub fn get_connection_stats(har: &Har) -> ConnectionStats {
+    let mut stats = ConnectionStats::default();
+
+    for entry in &har.log.entries {
+        match entry.connection.as_deref().filter(|c| !c.is_empty()) {
+            Some(connection) => *stats.connections.entry(connection.to_string()).or_insert(0) += 1,
+            None => stats.unknown += 1,
+        }
+    }
+
+    stats
+}
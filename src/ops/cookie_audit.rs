@@ -0,0 +1,152 @@
+use crate::Har;
+use chrono::{DateTime, FixedOffset, NaiveDateTime};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+use super::cookies;
+
+/// Values set for each cookie name, in request order.
+type CookieValues = HashMap<String, Vec<String>>;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Finding {
+    /// A cookie set more than once with different values during one page load.
+    Overwrite {
+        cookie: String,
+        page: String,
+        values: Vec<String>,
+    },
+    /// A cookie that lives longer than the allowed number of days.
+    LongExpiry {
+        cookie: String,
+        host: String,
+        /// The `Expires` date or `Max-Age` as set.
+        expiry: String,
+        days: i64,
+    },
+}
+
+/// Audit cookies, flagging any that expire more than `max_age_days` after
+/// they're set.
+pub fn cookie_audit(har: &Har, max_age_days: i64) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    find_overwrites(har, &mut findings);
+    find_long_expiry(har, max_age_days, &mut findings);
+    findings
+}
+
+fn find_long_expiry(har: &Har, max_age_days: i64, findings: &mut Vec<Finding>) {
+    for entry in &har.log.entries {
+        let host = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_default();
+        // expiry dates are relative to when the capture was made, not now
+        let set_at = DateTime::parse_from_rfc3339(&entry.started_date_time).ok();
+
+        for cookie in cookies::set_cookies(&entry.response) {
+            // max-age takes precedence over expires
+            let (expiry, days) = if let Some(max_age) = cookie.max_age {
+                (format!("Max-Age={}", max_age), max_age / 86_400)
+            } else if let (Some(expires), Some(set_at)) = (&cookie.expires, set_at) {
+                let Some(expires_at) = parse_expires(expires) else {
+                    log::debug!("Failed to parse cookie expiry: {}", expires);
+                    continue;
+                };
+                (expires.clone(), (expires_at - set_at).num_days())
+            } else {
+                continue;
+            };
+
+            if days > max_age_days {
+                findings.push(Finding::LongExpiry {
+                    cookie: cookie.name,
+                    host: host.clone(),
+                    expiry,
+                    days,
+                });
+            }
+        }
+    }
+}
+
+/// Parse an `Expires` attribute, normally an HTTP date but the cookie arrays
+/// of some tools use ISO 8601.
+fn parse_expires(expires: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc2822(expires)
+        .or_else(|_| DateTime::parse_from_rfc3339(expires))
+        .ok()
+        .or_else(|| {
+            // the old Netscape format with dashes, i.e. Wed, 21-Oct-2099 07:28:00 GMT
+            NaiveDateTime::parse_from_str(expires, "%a, %d-%b-%Y %H:%M:%S GMT")
+                .ok()
+                .map(|dt| dt.and_utc().fixed_offset())
+        })
+}
+
+fn find_overwrites(har: &Har, findings: &mut Vec<Finding>) {
+    // cookie values per page, in order of first appearance of the page
+    let mut pages: Vec<(Option<&str>, CookieValues)> = Vec::new();
+
+    for entry in &har.log.entries {
+        let pageref = entry.pageref.as_deref();
+        let index = match pages.iter().position(|(p, _)| *p == pageref) {
+            Some(index) => index,
+            None => {
+                pages.push((pageref, HashMap::new()));
+                pages.len() - 1
+            }
+        };
+
+        for cookie in cookies::set_cookies(&entry.response) {
+            pages[index]
+                .1
+                .entry(cookie.name)
+                .or_default()
+                .push(cookie.value);
+        }
+    }
+
+    for (pageref, cookies) in pages {
+        let page = page_label(har, pageref);
+
+        let mut cookies: Vec<_> = cookies.into_iter().collect();
+        cookies.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (cookie, values) in cookies {
+            // setting the same value again isn't a conflict
+            if values.iter().all(|v| *v == values[0]) {
+                continue;
+            }
+
+            findings.push(Finding::Overwrite {
+                cookie,
+                page: page.clone(),
+                values,
+            });
+        }
+    }
+}
+
+fn page_label(har: &Har, pageref: Option<&str>) -> String {
+    let Some(pageref) = pageref else {
+        return "(no page)".to_string();
+    };
+
+    let title = har
+        .log
+        .pages
+        .iter()
+        .flatten()
+        .find(|page| page.id == pageref)
+        .map(|page| page.title.as_str())
+        .filter(|title| !title.is_empty());
+
+    match title {
+        Some(title) => format!("{} ({})", pageref, title),
+        None => pageref.to_string(),
+    }
+}
@@ -0,0 +1,47 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::cookies;
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct CookieFlow {
+    /// Number of times the server set the cookie.
+    pub set: usize,
+    /// Number of requests the client sent the cookie with.
+    pub sent: usize,
+    /// Number of requests sending the cookie after it was first set.
+    pub sent_after_set: usize,
+}
+
+impl CookieFlow {
+    pub fn never_used(&self) -> bool {
+        self.set > 0 && self.sent_after_set == 0
+    }
+
+    pub fn never_set(&self) -> bool {
+        self.set == 0 && self.sent > 0
+    }
+}
+
+pub fn cookie_flow(har: &Har) -> BTreeMap<String, CookieFlow> {
+    let mut flows: BTreeMap<String, CookieFlow> = BTreeMap::new();
+
+    // entries are in request order, so a cookie set earlier is seen first
+    for entry in &har.log.entries {
+        for (name, _) in cookies::sent_cookies(&entry.request) {
+            let flow = flows.entry(name).or_default();
+            flow.sent += 1;
+            if flow.set > 0 {
+                flow.sent_after_set += 1;
+            }
+        }
+
+        for cookie in cookies::set_cookies(&entry.response) {
+            flows.entry(cookie.name).or_default().set += 1;
+        }
+    }
+
+    flows
+}
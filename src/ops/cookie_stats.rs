@@ -0,0 +1,78 @@
+use crate::har::{Har, Request};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::cookies;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct LargestCookieHeader {
+    pub request_num: usize,
+    pub url: String,
+    pub size: usize,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct CookieStats {
+    pub with_cookies: usize,
+    pub without_cookies: usize,
+    /// Mean number of cookies sent per request, over all requests.
+    pub mean_cookies: f64,
+    pub largest: Option<LargestCookieHeader>,
+}
+
+pub fn cookie_stats(har: &Har) -> CookieStats {
+    let mut with_cookies = 0;
+    let mut total_cookies = 0;
+    let mut largest: Option<LargestCookieHeader> = None;
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let sent = cookies::sent_cookies(&entry.request);
+        if sent.is_empty() {
+            continue;
+        }
+
+        with_cookies += 1;
+        total_cookies += sent.len();
+
+        let size = cookie_header_size(&entry.request, &sent);
+        if largest.as_ref().is_none_or(|l| size > l.size) {
+            largest = Some(LargestCookieHeader {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                size,
+            });
+        }
+    }
+
+    let requests = har.log.entries.len();
+    CookieStats {
+        with_cookies,
+        without_cookies: requests - with_cookies,
+        mean_cookies: if requests == 0 {
+            0.0
+        } else {
+            total_cookies as f64 / requests as f64
+        },
+        largest,
+    }
+}
+
+/// Bytes in the request's `Cookie` header, rebuilt from the cookies if it
+/// wasn't recorded.
+fn cookie_header_size(request: &Request, sent: &[(String, String)]) -> usize {
+    let recorded: usize = request
+        .headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("cookie"))
+        .map(|h| h.value.len())
+        .sum();
+
+    if recorded > 0 {
+        return recorded;
+    }
+
+    sent.iter()
+        .map(|(name, value)| name.len() + value.len() + 1)
+        .sum::<usize>()
+        + 2 * sent.len().saturating_sub(1)
+}
@@ -0,0 +1,87 @@
+use crate::har::{Request, Response};
+
+/// A cookie set by a response.
+#[derive(Clone, Debug)]
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+    pub expires: Option<String>,
+    pub max_age: Option<i64>,
+}
+
+/// Get the cookies set by a response.
+///
+/// The `Set-Cookie` headers are preferred as they carry every attribute,
+/// falling back to the parsed cookie array.
+pub fn set_cookies(response: &Response) -> Vec<SetCookie> {
+    let from_headers: Vec<SetCookie> = response
+        .headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("set-cookie"))
+        // some tools join multiple cookies into one header with newlines
+        .flat_map(|h| h.value.lines())
+        .filter_map(parse_set_cookie)
+        .collect();
+
+    if !from_headers.is_empty() {
+        return from_headers;
+    }
+
+    response
+        .cookies
+        .iter()
+        .map(|c| SetCookie {
+            name: c.name.clone(),
+            value: c.value.clone(),
+            expires: c.expires.clone(),
+            max_age: None,
+        })
+        .collect()
+}
+
+fn parse_set_cookie(header: &str) -> Option<SetCookie> {
+    let mut parts = header.split(';');
+    let (name, value) = parts.next()?.split_once('=')?;
+
+    let mut cookie = SetCookie {
+        name: name.trim().to_string(),
+        value: value.trim().to_string(),
+        expires: None,
+        max_age: None,
+    };
+
+    for attribute in parts {
+        let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+        match key.trim().to_ascii_lowercase().as_str() {
+            "expires" => cookie.expires = Some(value.trim().to_string()),
+            "max-age" => cookie.max_age = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(cookie)
+}
+
+/// Get the name/value pairs of the cookies sent with a request.
+///
+/// Uses the parsed cookie array, falling back to the `Cookie` header.
+pub fn sent_cookies(request: &Request) -> Vec<(String, String)> {
+    if !request.cookies.is_empty() {
+        return request
+            .cookies
+            .iter()
+            .map(|c| (c.name.clone(), c.value.clone()))
+            .collect();
+    }
+
+    request
+        .headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("cookie"))
+        .flat_map(|h| h.value.split(';'))
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
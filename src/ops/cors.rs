@@ -0,0 +1,125 @@
+use crate::har::{Har, Header};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Methods that never need to be listed in `Access-Control-Allow-Methods`.
+const SIMPLE_METHODS: [&str; 3] = ["GET", "HEAD", "POST"];
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CorsProblem {
+    /// The request's origin isn't allowed by the preflight.
+    OriginNotAllowed {
+        origin: String,
+        allowed: Option<String>,
+    },
+    /// The request's method isn't allowed by the preflight.
+    MethodNotAllowed {
+        method: String,
+        allowed: Option<String>,
+    },
+    /// A header the preflight asked about isn't allowed.
+    HeaderNotAllowed {
+        header: String,
+        allowed: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct CorsPair {
+    pub preflight_num: usize,
+    pub request_num: usize,
+    pub url: String,
+    pub method: String,
+    pub problems: Vec<CorsProblem>,
+}
+
+fn header_value<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+/// Split a comma separated header into trimmed, non-empty items.
+fn list_items(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
+
+/// Pair each `OPTIONS` preflight with the next request to the same URL, and
+/// check the preflight's response allows it.
+pub fn cors_pairs(har: &Har) -> Vec<CorsPair> {
+    let entries = &har.log.entries;
+    let mut pairs = Vec::new();
+
+    for (i, preflight) in entries.iter().enumerate() {
+        let preflight_request = &preflight.request;
+        if !preflight_request.method.eq_ignore_ascii_case("OPTIONS")
+            || header_value(&preflight_request.headers, "access-control-request-method").is_none()
+        {
+            continue;
+        }
+
+        let Some((j, actual)) = entries.iter().enumerate().skip(i + 1).find(|(_, e)| {
+            e.request.url == preflight_request.url
+                && !e.request.method.eq_ignore_ascii_case("OPTIONS")
+        }) else {
+            log::debug!("No request follows the preflight in request {}", i + 1);
+            continue;
+        };
+
+        let allow = |name| header_value(&preflight.response.headers, name);
+        let mut problems = Vec::new();
+
+        if let Some(origin) = header_value(&actual.request.headers, "origin") {
+            let allowed = allow("access-control-allow-origin");
+            if !allowed.is_some_and(|a| a.trim() == "*" || a.trim() == origin) {
+                problems.push(CorsProblem::OriginNotAllowed {
+                    origin: origin.to_string(),
+                    allowed: allowed.map(String::from),
+                });
+            }
+        }
+
+        let method = &actual.request.method;
+        let allowed_methods = allow("access-control-allow-methods");
+        let method_allowed = SIMPLE_METHODS.contains(&method.to_ascii_uppercase().as_str())
+            || allowed_methods.is_some_and(|allowed| {
+                list_items(allowed).any(|m| m == "*" || m.eq_ignore_ascii_case(method))
+            });
+        if !method_allowed {
+            problems.push(CorsProblem::MethodNotAllowed {
+                method: method.clone(),
+                allowed: allowed_methods.map(String::from),
+            });
+        }
+
+        let requested_headers =
+            header_value(&preflight_request.headers, "access-control-request-headers");
+        let allowed_headers = allow("access-control-allow-headers");
+        for header in requested_headers.into_iter().flat_map(list_items) {
+            let header_allowed = allowed_headers.is_some_and(|allowed| {
+                list_items(allowed).any(|h| h == "*" || h.eq_ignore_ascii_case(header))
+            });
+            if !header_allowed {
+                problems.push(CorsProblem::HeaderNotAllowed {
+                    header: header.to_string(),
+                    allowed: allowed_headers.map(String::from),
+                });
+            }
+        }
+
+        pairs.push(CorsPair {
+            preflight_num: i + 1,
+            request_num: j + 1,
+            url: actual.request.url.clone(),
+            method: method.clone(),
+            problems,
+        });
+    }
+
+    pairs
+}
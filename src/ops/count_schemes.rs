@@ -1,8 +1,12 @@
 use crate::Har;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use url::Url;
 
-pub fn get_counts(har: &Har, counts: &mut HashMap<String, usize>) {
+/// Number of requests using each URL scheme.
+pub type SchemeCounts = BTreeMap<String, usize>;
+
+pub fn get_counts(har: &Har) -> SchemeCounts {
+    let mut counts = SchemeCounts::new();
     for entry in &har.log.entries {
         let url_str = &entry.request.url;
         let count_key = match Url::parse(url_str) {
@@ -14,4 +18,6 @@ pub fn get_counts(har: &Har, counts: &mut HashMap<String, usize>) {
         };
         *counts.entry(count_key).or_insert(0) += 1;
     }
+
+    counts
 }
@@ -1,24 +1,62 @@
 use crate::Har;
-use std::{collections::HashMap, net::IpAddr};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::IpAddr,
+};
 use tldextract::TldExtractor;
 use url::Url;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, JsonSchema)]
 pub struct DomainNode {
     pub count: usize,
-    pub children: HashMap<String, DomainNode>,
+    pub children: BTreeMap<String, DomainNode>,
+}
+
+impl DomainNode {
+    /// Number of requests counted under this node, the root's own count is
+    /// never incremented so this sums its children.
+    pub fn total(&self) -> usize {
+        self.children.values().map(|child| child.count).sum()
+    }
+
+    /// Number of levels below this node, 0 for a leaf.
+    pub fn depth(&self) -> usize {
+        self.children
+            .values()
+            .map(|child| child.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Node that `data:` and `blob:` URLs are counted under when included.
+pub const DATA_URI_NODE: &str = "(data-uri)";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TreeOptions {
+    /// Merge the tld and the sld, i.e. `example.com` rather than `com` then `example`.
+    pub merge_tld: bool,
+    /// Count `www.example.com` as `example.com`.
+    pub merge_www: bool,
+    /// Count `data:` and `blob:` URLs under [`DATA_URI_NODE`] instead of skipping them.
+    pub include_data_uris: bool,
 }
 
 pub fn build_domain_tree(
     har: &Har,
     tree: &mut DomainNode,
     tld_extractor: &TldExtractor,
-    merge_tld: bool,
+    options: TreeOptions,
 ) {
+    // extraction is slow, so only do it once per distinct host
+    let mut parts_cache: HashMap<String, Vec<String>> = HashMap::new();
+
     // iterate through URLs in entries in HAR
     for entry in &har.log.entries {
         let url = &entry.request.url;
-        process_url(url, tree, tld_extractor, merge_tld);
+        process_url(url, tree, tld_extractor, options, &mut parts_cache);
     }
 }
 
@@ -26,7 +64,8 @@ fn process_url(
     url_str: &str,
     tree: &mut DomainNode,
     tld_extractor: &TldExtractor,
-    merge_tld: bool,
+    options: TreeOptions,
+    parts_cache: &mut HashMap<String, Vec<String>>,
 ) {
     // parse URL
     let Ok(parsed_url) = Url::parse(url_str) else {
@@ -35,9 +74,13 @@ fn process_url(
     };
 
     // get parts of URL
-    let parts = if parsed_url.scheme() == "data" {
-        // if using data scheme, use "data:" as though it were a TLD
-        vec!["data:".to_string()]
+    let parts = if matches!(parsed_url.scheme(), "data" | "blob") {
+        // inline or in-memory content rather than a request to a host
+        if !options.include_data_uris {
+            log::debug!("Skipping {} URL", parsed_url.scheme());
+            return;
+        }
+        vec![DATA_URI_NODE.to_string()]
     } else {
         // get host from parsed url
         let Some(host) = parsed_url.host_str() else {
@@ -46,7 +89,17 @@ fn process_url(
         };
 
         // get the parts of the host string
-        get_domain_parts(host, tld_extractor, merge_tld)
+        match parts_cache.get(host) {
+            Some(parts) => {
+                log::trace!("TLD cache hit for {}", host);
+                parts.clone()
+            }
+            None => {
+                let parts = get_domain_parts(host, tld_extractor, options);
+                parts_cache.insert(host.to_string(), parts.clone());
+                parts
+            }
+        }
     };
 
     // add the parts to the tree
@@ -57,7 +110,7 @@ fn process_url(
     }
 }
 
-fn get_domain_parts(host: &str, tld_extractor: &TldExtractor, merge_tld: bool) -> Vec<String> {
+fn get_domain_parts(host: &str, tld_extractor: &TldExtractor, options: TreeOptions) -> Vec<String> {
     // handle IP addresses
     if let Ok(ip) = host.parse::<IpAddr>() {
         return vec![format!("ip:{}", ip)];
@@ -72,7 +125,7 @@ fn get_domain_parts(host: &str, tld_extractor: &TldExtractor, merge_tld: bool) -
     let mut parts = Vec::new();
 
     // add domain and suffix
-    match (merge_tld, &extracted.domain, &extracted.suffix) {
+    match (options.merge_tld, &extracted.domain, &extracted.suffix) {
         (true, Some(domain), Some(suffix)) => parts.push(format!("{}.{}", domain, suffix)),
         (true, None, Some(suffix)) => parts.push(suffix.clone()),
         (true, Some(domain), None) => parts.push(domain.clone()),
@@ -85,8 +138,15 @@ fn get_domain_parts(host: &str, tld_extractor: &TldExtractor, merge_tld: bool) -
         _ => (),
     }
 
+    // www directly in front of the registered domain is usually the same site,
+    // the extractor has already put any multi-label suffix like co.uk aside
+    let subdomain = extracted
+        .subdomain
+        .as_ref()
+        .filter(|subdomain| !(options.merge_www && subdomain.eq_ignore_ascii_case("www")));
+
     // add subdomain
-    if let Some(subdomain) = &extracted.subdomain {
+    if let Some(subdomain) = subdomain {
         parts.extend(subdomain.split('.').rev().map(String::from));
     } else {
         parts.push(String::new())
@@ -99,33 +159,3 @@ fn get_domain_parts(host: &str, tld_extractor: &TldExtractor, merge_tld: bool) -
 
     parts
 }
-
-pub fn print_tree<F, K>(node: &DomainNode, sort_closure: &mut F)
-where
-    F: FnMut(&(&String, &DomainNode)) -> K,
-    K: Ord,
-{
-    // recursively print the tree levels
-    print_level(&node.children, 0, sort_closure);
-}
-
-fn print_level<F, K>(children: &HashMap<String, DomainNode>, depth: usize, sort_closure: &mut F)
-where
-    F: FnMut(&(&String, &DomainNode)) -> K,
-    K: Ord,
-{
-    // get entries as a vector
-    let mut entries: Vec<_> = children.iter().collect();
-    // sort them
-    entries.sort_by_key(|e| sort_closure(e));
-
-    // iterate through entries
-    for (key, node) in entries {
-        // print each entry
-        let indent = "    ".repeat(depth);
-        println!("{}{} ({})", indent, key, node.count);
-
-        // print its children
-        print_level(&node.children, depth + 1, sort_closure);
-    }
-}
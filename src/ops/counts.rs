@@ -0,0 +1,13 @@
+use std::cmp::Reverse;
+
+/// Counts from most to least frequent, with ties in key order so output is
+/// reproducible.
+pub fn by_frequency<'a, K, I>(counts: I) -> Vec<(&'a K, usize)>
+where
+    K: Ord + 'a,
+    I: IntoIterator<Item = (&'a K, &'a usize)>,
+{
+    let mut counts_vec: Vec<(&K, usize)> = counts.into_iter().map(|(k, &n)| (k, n)).collect();
+    counts_vec.sort_by(|a, b| Reverse(a.1).cmp(&Reverse(b.1)).then_with(|| a.0.cmp(b.0)));
+    counts_vec
+}
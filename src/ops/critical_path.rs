@@ -0,0 +1,228 @@
+//! Approximate the chain of requests that held up each page's `onLoad`.
+//!
+//! A HAR doesn't record which request led to which, so each request is given
+//! the parent that most likely triggered it, from the requests that had
+//! already finished when it started:
+//!
+//! 1. a response redirecting to its URL,
+//! 2. otherwise the latest request to its `Referer`,
+//! 3. otherwise the latest html, css or javascript response, as only those
+//!    discover more requests,
+//! 4. otherwise none, it starts a chain.
+//!
+//! The critical path is then the chain ending at the page's last request to
+//! finish before `onLoad`, or its last request if the page has no `onLoad`.
+
+use crate::{har::Entry, Har};
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::mime;
+
+/// Slack allowed when checking a request finished before another started, as
+/// tools round the start time and duration separately.
+const TOLERANCE_MS: f64 = 1.0;
+
+/// How a request on the path is linked to the one before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Link {
+    /// The first request of the chain.
+    Start,
+    /// The previous response redirected to it.
+    Redirect,
+    /// Its `Referer` is the previous request.
+    Referer,
+    /// The previous request is the last document, stylesheet or script to finish
+    /// before it started.
+    Preceding,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PathStep {
+    pub request_num: usize,
+    pub url: String,
+    pub link: Link,
+    /// Milliseconds from the page starting to the request starting.
+    pub start_ms: f64,
+    pub duration_ms: f64,
+    /// Milliseconds from the page starting to the request finishing.
+    pub end_ms: f64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct CriticalPath {
+    /// `None` for a capture without pages.
+    pub pageref: Option<String>,
+    pub title: Option<String>,
+    pub on_load_ms: Option<f64>,
+    pub steps: Vec<PathStep>,
+}
+
+/// A request of the page, times are milliseconds since the epoch.
+#[derive(Clone, Copy)]
+struct Node<'a> {
+    request_num: usize,
+    entry: &'a Entry,
+    start: f64,
+    end: f64,
+}
+
+fn start_ms(started_date_time: &str) -> Option<f64> {
+    DateTime::parse_from_rfc3339(started_date_time)
+        .ok()
+        .map(|time| time.timestamp_micros() as f64 / 1000.0)
+}
+
+/// Whether a response can lead to more requests, html, css and javascript.
+fn discovers_requests(entry: &Entry) -> bool {
+    let Some(mime_type) = entry
+        .response
+        .content
+        .as_ref()
+        .and_then(|content| content.mime_type.as_deref())
+    else {
+        return false;
+    };
+
+    let mime_type = mime::normalise(mime_type);
+    matches!(
+        mime_type.as_str(),
+        "text/html" | "application/xhtml+xml" | "text/css"
+    ) || mime_type.contains("javascript")
+        || mime_type.contains("ecmascript")
+}
+
+/// The request that most likely triggered `node`, as listed in the module docs.
+fn parent(nodes: &[Node], node: &Node) -> Option<(usize, Link)> {
+    let finished = || {
+        nodes
+            .iter()
+            .enumerate()
+            // starting strictly earlier keeps zero length requests from looping
+            .filter(|(_, other)| other.start < node.start)
+            .filter(|(_, other)| other.end <= node.start + TOLERANCE_MS)
+    };
+    let latest = |candidates: &mut dyn Iterator<Item = (usize, &Node)>| {
+        candidates
+            .max_by(|(_, a), (_, b)| a.end.total_cmp(&b.end))
+            .map(|(i, _)| i)
+    };
+
+    let url = &node.entry.request.url;
+    if let Some(i) = latest(&mut finished().filter(|(_, other)| {
+        !other.entry.response.redirect_url.is_empty() && other.entry.response.redirect_url == *url
+    })) {
+        return Some((i, Link::Redirect));
+    }
+
+    let referer = node
+        .entry
+        .request
+        .headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case("referer"))
+        .map(|h| h.value.as_str());
+    if let Some(referer) = referer {
+        if let Some(i) =
+            latest(&mut finished().filter(|(_, other)| other.entry.request.url == referer))
+        {
+            return Some((i, Link::Referer));
+        }
+    }
+
+    latest(&mut finished().filter(|(_, other)| discovers_requests(other.entry)))
+        .map(|i| (i, Link::Preceding))
+}
+
+/// Follow the parents back from the request that finished last before the
+/// deadline, or the last to finish if there's none.
+fn critical_steps(nodes: &[Node], page_start: f64, deadline: Option<f64>) -> Vec<PathStep> {
+    let by_end = |a: &&Node, b: &&Node| a.end.total_cmp(&b.end);
+    let last = nodes
+        .iter()
+        .filter(|node| deadline.is_none_or(|deadline| node.end <= deadline + TOLERANCE_MS))
+        .max_by(by_end)
+        .or_else(|| nodes.iter().max_by(by_end));
+
+    let mut steps = Vec::new();
+    let mut current = last;
+
+    while let Some(node) = current {
+        let parent = parent(nodes, node);
+        steps.push(PathStep {
+            request_num: node.request_num,
+            url: node.entry.request.url.clone(),
+            link: parent.map_or(Link::Start, |(_, link)| link),
+            start_ms: node.start - page_start,
+            duration_ms: node.end - node.start,
+            end_ms: node.end - page_start,
+        });
+        current = parent.map(|(i, _)| &nodes[i]);
+    }
+
+    steps.reverse();
+    steps
+}
+
+/// Find the critical path of each page, or of the whole capture if it has no pages.
+pub fn critical_paths(har: &Har) -> Vec<CriticalPath> {
+    let nodes: Vec<Node> = har
+        .log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let start = start_ms(&entry.started_date_time)?;
+            Some(Node {
+                request_num: i + 1,
+                entry,
+                start,
+                end: start + entry.time.max(0.0),
+            })
+        })
+        .collect();
+
+    let pages = har.log.pages.as_deref().unwrap_or_default();
+    if pages.is_empty() {
+        let Some(page_start) = nodes.iter().map(|node| node.start).min_by(f64::total_cmp) else {
+            return Vec::new();
+        };
+        return vec![CriticalPath {
+            pageref: None,
+            title: None,
+            on_load_ms: None,
+            steps: critical_steps(&nodes, page_start, None),
+        }];
+    }
+
+    pages
+        .iter()
+        .filter_map(|page| {
+            let page_nodes: Vec<Node> = nodes
+                .iter()
+                .filter(|node| node.entry.pageref.as_deref() == Some(page.id.as_str()))
+                .copied()
+                .collect();
+            if page_nodes.is_empty() {
+                return None;
+            }
+
+            let page_start = start_ms(&page.started_date_time).unwrap_or_else(|| {
+                page_nodes
+                    .iter()
+                    .map(|node| node.start)
+                    .fold(f64::INFINITY, f64::min)
+            });
+            let on_load = page.page_timings.on_load.filter(|&on_load| on_load >= 0.0);
+
+            Some(CriticalPath {
+                pageref: Some(page.id.clone()),
+                title: Some(page.title.clone()),
+                on_load_ms: on_load,
+                steps: critical_steps(&page_nodes, page_start, on_load.map(|ms| page_start + ms)),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,67 @@
+use crate::har::Content;
+use base64::{
+    alphabet,
+    engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+
+// accept both padded and unpadded input for either alphabet
+const CONFIG: GeneralPurposeConfig =
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const STANDARD: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, CONFIG);
+const URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, CONFIG);
+
+/// Decode base64, trying the standard alphabet first then falling back to base64url.
+pub fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    // some tools wrap encoded bodies across lines
+    let cleaned: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+
+    STANDARD
+        .decode(&cleaned)
+        .or_else(|_| URL_SAFE.decode(&cleaned))
+        .ok()
+}
+
+/// Get the body bytes of a response, decoding them if they're base64 encoded.
+pub fn decode_body(content: &Content) -> Option<Vec<u8>> {
+    let text = content.text.as_ref()?;
+
+    match content.encoding.as_deref() {
+        Some("base64") => {
+            let decoded = decode_base64(text);
+            if decoded.is_none() {
+                log::debug!("Failed to decode base64 body of {} bytes", text.len());
+            }
+            decoded
+        }
+        _ => Some(text.as_bytes().to_vec()),
+    }
+}
+
+/// Get the body of a response as text, if it decodes to valid UTF-8.
+pub fn decode_body_text(content: &Content) -> Option<String> {
+    String::from_utf8(decode_body(content)?).ok()
+}
+
+/// Replace base64 encoded response bodies in a raw HAR with their decoded text.
+///
+/// Bodies that don't decode to valid UTF-8 are left encoded.
+pub fn decode_bodies(har: &mut json::JsonValue) {
+    for entry in har["log"]["entries"].members_mut() {
+        let content = &mut entry["response"]["content"];
+        if content["encoding"] != "base64" {
+            continue;
+        }
+
+        let Some(text) = content["text"]
+            .as_str()
+            .and_then(decode_base64)
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        else {
+            continue;
+        };
+
+        content["text"] = text.into();
+        content.remove("encoding");
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Har;
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::bytes;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DevtoolsSummary {
+    pub requests: usize,
+    pub transferred: u64,
+    pub resources: u64,
+    /// Time from the first request starting until the last one finished.
+    pub finish: Option<f64>,
+    pub dom_content_loaded: Option<f64>,
+    pub load: Option<f64>,
+}
+
+pub fn devtools_summary(har: &Har) -> DevtoolsSummary {
+    let (transferred, resources) = bytes::total_bytes(har);
+
+    // span between the earliest start and the latest end
+    let mut first_start: Option<f64> = None;
+    let mut last_end: Option<f64> = None;
+    for entry in &har.log.entries {
+        let Ok(start) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+            continue;
+        };
+        let start = start.timestamp_millis() as f64;
+        let end = start + entry.time.max(0.0);
+
+        first_start = Some(first_start.map_or(start, |s| s.min(start)));
+        last_end = Some(last_end.map_or(end, |e| e.max(end)));
+    }
+
+    // devtools reports the timings of the main page
+    let page_timings = har
+        .log
+        .pages
+        .as_ref()
+        .and_then(|pages| pages.first())
+        .map(|page| &page.page_timings);
+
+    DevtoolsSummary {
+        requests: har.log.entries.len(),
+        transferred,
+        resources,
+        finish: first_start.zip(last_end).map(|(start, end)| end - start),
+        dom_content_loaded: page_timings
+            .and_then(|t| t.on_content_load)
+            .filter(|&t| t >= 0.0),
+        load: page_timings.and_then(|t| t.on_load).filter(|&t| t >= 0.0),
+    }
+}
@@ -1,41 +1,78 @@
 use anyhow::Result;
-use colored::Colorize;
 use hickory_resolver::{
     proto::rr::{Record, RecordType},
     Resolver, TokioResolver,
 };
 
 use crate::har::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 use super::list_domains;
 
-pub async fn dnssec_audit(har: &Har) -> Result<()> {
-    let mut domains: Vec<String> = list_domains::list_domains(har);
-    domains.sort_by_key(|x| x.chars().rev().collect::<String>());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DnssecStatus {
+    Signed,
+    Unsigned,
+    LookupFailed,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DnssecResult {
+    pub domain: String,
+    pub status: DnssecStatus,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DnssecSummary {
+    pub signed: usize,
+    pub domains: usize,
+    pub lookup_failed: usize,
+}
+
+/// Count the statuses of audited domains.
+pub fn summarise_dnssec(results: &[DnssecResult]) -> DnssecSummary {
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+
+    DnssecSummary {
+        signed: count(DnssecStatus::Signed),
+        domains: results.len(),
+        lookup_failed: count(DnssecStatus::LookupFailed),
+    }
+}
+
+/// The unique domains that are looked up, sorted by their reversed name so
+/// subdomains sit together.
+pub fn lookup_domains(har: &Har) -> Vec<String> {
+    list_domains::sorted_domains(har)
+}
+
+pub async fn dnssec_audit(har: &Har) -> Result<Vec<DnssecResult>> {
+    let domains = lookup_domains(har);
 
     let resolver = Resolver::builder_tokio()?.build();
+    let mut results = Vec::new();
 
     for domain in domains {
         let resp = resolver.lookup(domain.clone() + ".", RecordType::ANY);
-        let Ok(resp) = resp.await else {
-            println!("{}: {}", domain.bold(), "DNS lookup failed".red());
-            continue;
+        let status = match resp.await {
+            Ok(resp)
+                if resp
+                    .records()
+                    .iter()
+                    .any(|r| r.record_type() == RecordType::RRSIG) =>
+            {
+                DnssecStatus::Signed
+            }
+            Ok(_) => DnssecStatus::Unsigned,
+            Err(_) => DnssecStatus::LookupFailed,
         };
 
-        let mut sig_found = false;
-
-        for record in resp.records() {
-            sig_found |= record.record_type() == RecordType::RRSIG;
-        }
-
-        if sig_found {
-            println!("{}: {}", domain.bold(), "Signature found.".green())
-        } else {
-            println!("{}: {}", domain.bold(), "No signature found.".yellow())
-        }
+        results.push(DnssecResult { domain, status });
     }
 
-    Ok(())
+    Ok(results)
 }
 
 async fn get_dns_records(resolver: &TokioResolver, domain: &str) -> Vec<Record> {
@@ -59,47 +96,48 @@ async fn get_dns_records(resolver: &TokioResolver, domain: &str) -> Vec<Record>
     let mut records = Vec::new();
 
     for rt in record_types {
-        match resolver.lookup(&fqdn, rt).await {
-            Ok(response) => {
-                records.extend(response.records().iter().cloned());
-            },
-            Err(_) => {},
+        if let Ok(response) = resolver.lookup(&fqdn, rt).await {
+            records.extend(response.records().iter().cloned());
         }
     }
 
     records
 }
 
-pub async fn dns_lookup(har: &Har) -> Result<()> {
-    let mut domains: Vec<String> = list_domains::list_domains(har);
-    domains.sort_by_key(|x| x.chars().rev().collect::<String>());
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DnsRecord {
+    pub record_type: String,
+    pub name: String,
+    pub ttl: u32,
+    pub data: String,
+}
 
-    let resolver = Resolver::builder_tokio()?.build();
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DomainRecords {
+    pub domain: String,
+    pub records: Vec<DnsRecord>,
+}
 
-    for domain in domains {
-        println!("{}:", domain.bold().blue());
-
-        let mut found_records = false;
-
-        for record in get_dns_records(&resolver, &domain).await {
-            found_records = true;
-            println!(
-                "[{:6}] {} - TTL: {} - {}",
-                format!("{}", record.record_type()).purple().bold(),
-                record.name().to_string().cyan(),
-                record.ttl().to_string().yellow(),
-                record
-                    .data()
-                    .to_string()
-            );
-        }
+pub async fn dns_lookup(har: &Har) -> Result<Vec<DomainRecords>> {
+    let domains = lookup_domains(har);
 
-        if !found_records {
-            println!("{}", "No DNS records found".red());
-        }
+    let resolver = Resolver::builder_tokio()?.build();
+    let mut results = Vec::new();
 
-        println!();
+    for domain in domains {
+        let records = get_dns_records(&resolver, &domain)
+            .await
+            .iter()
+            .map(|record| DnsRecord {
+                record_type: record.record_type().to_string(),
+                name: record.name().to_string(),
+                ttl: record.ttl(),
+                data: record.data().to_string(),
+            })
+            .collect();
+
+        results.push(DomainRecords { domain, records });
     }
 
-    Ok(())
+    Ok(results)
 }
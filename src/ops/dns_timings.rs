@@ -0,0 +1,71 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+use super::{stats::Stats, timing::Phase};
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DnsTiming {
+    pub host: String,
+    /// Requests to the host recording a DNS phase.
+    pub requests: usize,
+    /// Lookups that took any time, requests reusing a connection or a cached
+    /// lookup record `0` and are left out.
+    pub lookups: Stats,
+    pub max_ms: f64,
+    /// Whether the median lookup took at least the slow threshold.
+    pub slow: bool,
+}
+
+/// Aggregate the recorded DNS lookup time of each host, slowest median first.
+/// Requests without a DNS phase or recording `-1` are skipped, and hosts with
+/// no lookups taking any time are left out.
+pub fn dns_timings(har: &Har, slow_ms: f64) -> Vec<DnsTiming> {
+    let mut hosts: HashMap<String, (usize, Vec<f64>)> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let Some(dns) = entry
+            .timings
+            .as_ref()
+            .and_then(|timing| Phase::Dns.value(timing))
+        else {
+            continue;
+        };
+        let Some(host) = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+        else {
+            continue;
+        };
+
+        let (requests, lookups) = hosts.entry(host).or_default();
+        *requests += 1;
+        if dns > 0.0 {
+            lookups.push(dns);
+        }
+    }
+
+    let mut results: Vec<DnsTiming> = hosts
+        .into_iter()
+        .filter_map(|(host, (requests, lookups))| {
+            let stats = Stats::from_values(&lookups)?;
+            Some(DnsTiming {
+                host,
+                requests,
+                max_ms: lookups.iter().copied().fold(0.0, f64::max),
+                slow: stats.median >= slow_ms,
+                lookups: stats,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.lookups
+            .median
+            .total_cmp(&a.lookups.median)
+            .then_with(|| a.host.cmp(&b.host))
+    });
+    results
+}
@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{collections::HashSet, fs, net::IpAddr, path::Path};
+use tldextract::TldExtractor;
+use url::Url;
+
+use crate::Har;
+
+/// Registrable domains read from a newline-delimited list, i.e. `example.co.uk`.
+#[derive(Clone, Debug, Default)]
+pub struct DomainList {
+    domains: HashSet<String>,
+}
+
+impl DomainList {
+    /// Read a list with one domain per line, blank lines and `#` comments are skipped.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read domain list: {:?}", path))?;
+        Ok(Self::parse(&text))
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let domains = text
+            .lines()
+            .map(|line| line.trim().trim_end_matches('.'))
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect();
+
+        Self { domains }
+    }
+
+    pub fn contains(&self, domain: &str) -> bool {
+        self.domains.contains(&domain.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.domains.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Party {
+    /// The site's own domain, or one on the allowlist.
+    First,
+    /// A domain on the blocklist of known trackers.
+    Tracker,
+    /// Any other domain.
+    Third,
+}
+
+impl Party {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Party::First => "first",
+            Party::Tracker => "tracker",
+            Party::Third => "third",
+        }
+    }
+}
+
+/// Registrable domain of a URL's host, i.e. `example.co.uk` for
+/// `https://ads.example.co.uk/`. IP addresses are returned as they are, and
+/// `None` for URLs without a host.
+pub fn registrable_domain(url: &str, extractor: &TldExtractor) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?.trim_end_matches('.').to_lowercase();
+
+    if host.trim_matches(['[', ']']).parse::<IpAddr>().is_ok() {
+        return Some(host);
+    }
+
+    let Ok(extracted) = extractor.extract(&host) else {
+        log::debug!("Failed to extract TLD from: {}", host);
+        return Some(host);
+    };
+
+    match (extracted.domain, extracted.suffix) {
+        (Some(domain), Some(suffix)) => Some(format!("{}.{}", domain, suffix)),
+        (Some(part), None) | (None, Some(part)) => Some(part),
+        (None, None) => Some(host),
+    }
+}
+
+/// Sorts domains into first party, third party and trackers.
+#[derive(Debug)]
+pub struct PartyClassifier<'a> {
+    extractor: &'a TldExtractor,
+    site: Option<String>,
+    allowlist: DomainList,
+    blocklist: DomainList,
+}
+
+impl<'a> PartyClassifier<'a> {
+    /// The registrable domain of the capture's first request is first party, as
+    /// are domains on the allowlist. The allowlist wins over the blocklist.
+    pub fn new(
+        har: &Har,
+        extractor: &'a TldExtractor,
+        allowlist: DomainList,
+        blocklist: DomainList,
+    ) -> Self {
+        let site = har
+            .log
+            .entries
+            .iter()
+            .find_map(|entry| registrable_domain(&entry.request.url, extractor));
+
+        Self {
+            extractor,
+            site,
+            allowlist,
+            blocklist,
+        }
+    }
+
+    /// The registrable domain treated as the site's own.
+    pub fn site(&self) -> Option<&str> {
+        self.site.as_deref()
+    }
+
+    /// Classify a registrable domain, as from [`registrable_domain`].
+    pub fn classify(&self, domain: &str) -> Party {
+        if self.site.as_deref() == Some(domain) || self.allowlist.contains(domain) {
+            Party::First
+        } else if self.blocklist.contains(domain) {
+            Party::Tracker
+        } else {
+            Party::Third
+        }
+    }
+
+    /// Registrable domain of a request URL and its classification.
+    pub fn classify_url(&self, url: &str) -> Option<(String, Party)> {
+        let domain = registrable_domain(url, self.extractor)?;
+        let party = self.classify(&domain);
+        Some((domain, party))
+    }
+}
@@ -0,0 +1,69 @@
+use crate::har::{Har, Header};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Headers that are expected to repeat with different values.
+const REPEATABLE: [&str; 1] = ["set-cookie"];
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DuplicateHeader {
+    pub request_num: usize,
+    pub url: String,
+    /// Which headers the duplicate was in, `request` or `response`.
+    pub side: &'static str,
+    pub name: String,
+    /// Distinct values, in the order they appear.
+    pub values: Vec<String>,
+}
+
+pub fn duplicate_headers(har: &Har) -> Vec<DuplicateHeader> {
+    let mut results = Vec::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        for (side, headers) in [
+            ("request", &entry.request.headers),
+            ("response", &entry.response.headers),
+        ] {
+            for (name, values) in conflicting(headers) {
+                results.push(DuplicateHeader {
+                    request_num: i + 1,
+                    url: entry.request.url.clone(),
+                    side,
+                    name,
+                    values,
+                });
+            }
+        }
+    }
+
+    results
+}
+
+/// Header names appearing more than once with different values.
+///
+/// Names are compared case-insensitively and reported as first written.
+fn conflicting(headers: &[Header]) -> Vec<(String, Vec<String>)> {
+    // kept as a list to report in header order
+    let mut seen: Vec<(String, Vec<String>)> = Vec::new();
+
+    for header in headers {
+        if REPEATABLE.contains(&header.name.to_ascii_lowercase().as_str()) {
+            continue;
+        }
+
+        match seen
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&header.name))
+        {
+            Some((_, values)) => {
+                if !values.contains(&header.value) {
+                    values.push(header.value.clone());
+                }
+            }
+            None => seen.push((header.name.clone(), vec![header.value.clone()])),
+        }
+    }
+
+    seen.retain(|(_, values)| values.len() > 1);
+    seen
+}
@@ -0,0 +1,210 @@
+use crate::har::{Entry, Har};
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+const SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+#[derive(Debug, Serialize)]
+pub struct Collection {
+    pub info: Info,
+    pub item: Vec<Folder>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Info {
+    pub name: String,
+    pub schema: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Folder {
+    pub name: String,
+    pub item: Vec<Item>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Item {
+    pub name: String,
+    pub request: PostmanRequest,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostmanRequest {
+    pub method: String,
+    pub header: Vec<KeyValue>,
+    pub url: PostmanUrl,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Body>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostmanUrl {
+    pub raw: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub host: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<KeyValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Body {
+    pub mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub urlencoded: Option<Vec<KeyValue>>,
+}
+
+pub fn export_postman(har: &Har) -> Collection {
+    // name the collection after the first page, if there is one
+    let name = har
+        .log
+        .pages
+        .as_ref()
+        .and_then(|pages| pages.first())
+        .map(|page| page.title.clone())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "HAR export".to_string());
+
+    // folders are kept in order of first appearance of each host
+    let mut folders: Vec<Folder> = Vec::new();
+    let mut folder_index: HashMap<String, usize> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let host = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let index = *folder_index.entry(host.clone()).or_insert_with(|| {
+            folders.push(Folder {
+                name: host,
+                item: Vec::new(),
+            });
+            folders.len() - 1
+        });
+
+        folders[index].item.push(convert_entry(entry));
+    }
+
+    Collection {
+        info: Info {
+            name,
+            schema: SCHEMA.to_string(),
+        },
+        item: folders,
+    }
+}
+
+fn convert_entry(entry: &Entry) -> Item {
+    let request = &entry.request;
+    let url = convert_url(&request.url);
+
+    // name items like "GET /path"
+    let name = format!("{} /{}", request.method, url.path.join("/"));
+
+    // HTTP/2 pseudo-headers aren't real headers, so Postman rejects them
+    let header = request
+        .headers
+        .iter()
+        .filter(|h| !h.name.starts_with(':'))
+        .map(|h| KeyValue {
+            key: h.name.clone(),
+            value: h.value.clone(),
+        })
+        .collect();
+
+    let body = request.post_data.as_ref().map(|post_data| {
+        match &post_data.params {
+            // form bodies map onto Postman's urlencoded mode
+            Some(params)
+                if !params.is_empty()
+                    && post_data
+                        .mime_type
+                        .starts_with("application/x-www-form-urlencoded") =>
+            {
+                Body {
+                    mode: "urlencoded".to_string(),
+                    raw: None,
+                    urlencoded: Some(
+                        params
+                            .iter()
+                            .map(|p| KeyValue {
+                                key: p.name.clone(),
+                                value: p.value.clone().unwrap_or_default(),
+                            })
+                            .collect(),
+                    ),
+                }
+            }
+            _ => Body {
+                mode: "raw".to_string(),
+                raw: Some(post_data.text.clone()),
+                urlencoded: None,
+            },
+        }
+    });
+
+    Item {
+        name,
+        request: PostmanRequest {
+            method: request.method.clone(),
+            header,
+            url,
+            body,
+        },
+    }
+}
+
+fn convert_url(url_str: &str) -> PostmanUrl {
+    let Ok(parsed) = Url::parse(url_str) else {
+        // fall back to just the raw URL
+        return PostmanUrl {
+            raw: url_str.to_string(),
+            protocol: None,
+            host: Vec::new(),
+            port: None,
+            path: Vec::new(),
+            query: Vec::new(),
+        };
+    };
+
+    PostmanUrl {
+        raw: url_str.to_string(),
+        protocol: Some(parsed.scheme().to_string()),
+        host: parsed
+            .host_str()
+            .map(|h| h.split('.').map(String::from).collect())
+            .unwrap_or_default(),
+        port: parsed.port().map(|p| p.to_string()),
+        path: parsed
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        query: parsed
+            .query_pairs()
+            .map(|(key, value)| KeyValue {
+                key: key.into_owned(),
+                value: value.into_owned(),
+            })
+            .collect(),
+    }
+}
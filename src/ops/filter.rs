@@ -1,17 +1,336 @@
-use crate::Har;
-use chrono::{DateTime, Local};
+use crate::{har::Header, Har};
+use chrono::{
+    DateTime, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeZone,
+};
+use chrono_tz::Tz;
+use std::str::FromStr;
 
-pub fn filter_by_time(har: &mut Har, time: DateTime<Local>, after: bool) {
+use super::{body::BodySide, search_for, timing::Phase};
+
+/// A half-open `start:end` range of entry indices, either side may be omitted.
+#[derive(Debug, Clone, Copy)]
+pub struct IndexRange {
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+}
+
+/// Parse an `A:B` range where either bound may be omitted, `what` names the
+/// bounds in errors.
+fn parse_range<T>(s: &str, what: &str) -> Result<(Option<T>, Option<T>), String>
+where
+    T: FromStr + PartialOrd + std::fmt::Display,
+{
+    let (start, end) = s
+        .split_once(':')
+        .ok_or_else(|| format!("expected a range in the form A:B, got '{}'", s))?;
+
+    let parse_bound = |bound: &str| -> Result<Option<T>, String> {
+        if bound.is_empty() {
+            return Ok(None);
+        }
+        bound
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("invalid {} '{}'", what, bound))
+    };
+
+    let (start, end) = (parse_bound(start)?, parse_bound(end)?);
+
+    if let (Some(start), Some(end)) = (&start, &end) {
+        if start > end {
+            return Err(format!("range start {} is after end {}", start, end));
+        }
+    }
+
+    Ok((start, end))
+}
+
+impl FromStr for IndexRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = parse_range(s, "index")?;
+        Ok(IndexRange { start, end })
+    }
+}
+
+/// An inclusive `min:max` range of sizes in bytes, either side may be omitted.
+#[derive(Debug, Clone, Copy)]
+pub struct SizeRange {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+}
+
+impl FromStr for SizeRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (min, max) = parse_range(s, "size")?;
+        Ok(SizeRange { min, max })
+    }
+}
+
+/// A `Name[:value]` header to look for, the name is matched case-insensitively.
+#[derive(Debug, Clone)]
+pub struct HeaderMatch {
+    pub name: String,
+    /// Value the header must have, compared exactly after trimming whitespace.
+    pub value: Option<String>,
+}
+
+impl FromStr for HeaderMatch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = match s.split_once(':') {
+            Some((name, value)) => (name.trim(), Some(value.trim().to_string())),
+            None => (s.trim(), None),
+        };
+
+        if name.is_empty() {
+            return Err(format!(
+                "expected a header in the form Name or Name:value, got '{}'",
+                s
+            ));
+        }
+
+        Ok(HeaderMatch {
+            name: name.to_string(),
+            value,
+        })
+    }
+}
+
+impl HeaderMatch {
+    fn matches(&self, header: &Header) -> bool {
+        header.name.eq_ignore_ascii_case(&self.name)
+            && self
+                .value
+                .as_ref()
+                .is_none_or(|value| header.value.trim() == value)
+    }
+}
+
+/// A time given on the command line, with or without a UTC offset.
+#[derive(Debug, Clone, Copy)]
+pub enum TimeArg {
+    Absolute(DateTime<FixedOffset>),
+    Naive(NaiveDateTime),
+}
+
+impl FromStr for TimeArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(dt) = s.parse::<DateTime<FixedOffset>>() {
+            return Ok(TimeArg::Absolute(dt));
+        }
+
+        if let Ok(naive) = s.parse::<NaiveDateTime>() {
+            return Ok(TimeArg::Naive(naive));
+        }
+
+        if let Ok(naive) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f") {
+            return Ok(TimeArg::Naive(naive));
+        }
+
+        // a plain date means midnight
+        if let Ok(date) = s.parse::<NaiveDate>() {
+            return Ok(TimeArg::Naive(date.and_time(Default::default())));
+        }
+
+        Err(format!(
+            "invalid time '{}', expected i.e. 2024-05-01T10:00:00+01:00 or 2024-05-01T10:00:00",
+            s
+        ))
+    }
+}
+
+impl TimeArg {
+    /// Resolve the time, interpreting times without an offset in the timezone.
+    ///
+    /// Falls back to the local timezone when none is given.
+    pub fn resolve(&self, timezone: Option<Tz>) -> Result<DateTime<FixedOffset>, String> {
+        let naive = match self {
+            TimeArg::Absolute(dt) => return Ok(*dt),
+            TimeArg::Naive(naive) => naive,
+        };
+
+        let resolved = match timezone {
+            Some(tz) => to_fixed(tz.from_local_datetime(naive)),
+            None => to_fixed(Local.from_local_datetime(naive)),
+        };
+
+        resolved.ok_or_else(|| match timezone {
+            Some(tz) => format!("{} doesn't exist in {}", naive, tz),
+            None => format!("{} doesn't exist in the local timezone", naive),
+        })
+    }
+}
+
+fn to_fixed<T: TimeZone>(result: LocalResult<DateTime<T>>) -> Option<DateTime<FixedOffset>> {
+    // times repeated by a DST change resolve to the earlier one
+    result
+        .earliest()
+        .map(|dt| dt.with_timezone(&dt.offset().fix()))
+}
+
+/// A `phase:ms` threshold on a timing phase, i.e. `dns:100`.
+#[derive(Debug, Clone, Copy)]
+pub struct PhaseThreshold {
+    pub phase: Phase,
+    pub threshold: f64,
+}
+
+impl FromStr for PhaseThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (phase, threshold) = s
+            .split_once(':')
+            .ok_or_else(|| format!("expected a threshold in the form phase:ms, got '{}'", s))?;
+
+        Ok(PhaseThreshold {
+            phase: phase.parse()?,
+            threshold: threshold
+                .parse()
+                .map_err(|_| format!("invalid threshold '{}'", threshold))?,
+        })
+    }
+}
+
+/// Which side of a time entries are kept on, both include the time itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBound {
+    /// Keep entries starting at or before the time.
+    Before,
+    /// Keep entries starting at or after the time.
+    After,
+}
+
+/// Keep the entries whose `startedDateTime` is on the bound's side of the time.
+///
+/// Entries starting exactly at the time are kept by either bound, so the same
+/// time given to `--after` and `--before` keeps just the entries starting then.
+pub fn filter_by_time(har: &mut Har, time: DateTime<FixedOffset>, bound: TimeBound) {
     har.log.entries.retain(|entry| {
         let start_time = match DateTime::parse_from_rfc3339(&entry.started_date_time) {
             Ok(t) => t,
-            Err(_) => return false,
+            Err(e) => {
+                log::debug!(
+                    "Dropping entry with invalid startedDateTime {:?}: {}",
+                    entry.started_date_time,
+                    e
+                );
+                return false;
+            }
         };
 
-        if after {
-            start_time >= time
-        } else {
-            start_time <= time
+        match bound {
+            TimeBound::Before => start_time <= time,
+            TimeBound::After => start_time >= time,
         }
     });
 }
+
+pub fn filter_by_index(har: &mut Har, range: IndexRange) {
+    let len = har.log.entries.len();
+    let end = range.end.unwrap_or(len).min(len);
+    let start = range.start.unwrap_or(0).min(end);
+
+    har.log.entries.truncate(end);
+    har.log.entries.drain(..start);
+}
+
+/// Keep the entries after `request_num`, numbered from 1 as commands print them.
+pub fn filter_after_request(har: &mut Har, request_num: usize) {
+    filter_by_index(
+        har,
+        IndexRange {
+            start: Some(request_num),
+            end: None,
+        },
+    );
+}
+
+/// Keep the entries before `request_num`, numbered from 1 as commands print them.
+pub fn filter_before_request(har: &mut Har, request_num: usize) {
+    filter_by_index(
+        har,
+        IndexRange {
+            start: None,
+            end: Some(request_num.saturating_sub(1)),
+        },
+    );
+}
+
+/// Keep the entries whose response `bodySize` is within the range.
+///
+/// Entries with an unknown size are kept unless there's a minimum.
+pub fn filter_by_size(har: &mut Har, range: SizeRange) {
+    har.log.entries.retain(|entry| {
+        let Ok(size) = u64::try_from(entry.response.body_size) else {
+            return range.min.is_none();
+        };
+
+        range.min.is_none_or(|min| size >= min) && range.max.is_none_or(|max| size <= max)
+    });
+}
+
+/// Keep the entries with a non-empty body on the side, going by the content's
+/// `size` or text for responses and the post data for requests.
+pub fn filter_by_body_presence(har: &mut Har, side: BodySide) {
+    har.log.entries.retain(|entry| match side {
+        BodySide::Request => entry.request.post_data.as_ref().is_some_and(|post_data| {
+            !post_data.text.is_empty() || post_data.params.as_ref().is_some_and(|p| !p.is_empty())
+        }),
+        BodySide::Response => entry.response.content.as_ref().is_some_and(|content| {
+            content.size.is_some_and(|size| size > 0)
+                || content.text.as_ref().is_some_and(|text| !text.is_empty())
+        }),
+    });
+}
+
+/// Keep the entries whose request or response has the header.
+pub fn filter_by_header(har: &mut Har, header: &HeaderMatch) {
+    har.log.entries.retain(|entry| {
+        entry
+            .request
+            .headers
+            .iter()
+            .chain(&entry.response.headers)
+            .any(|h| header.matches(h))
+    });
+}
+
+/// Keep the entries within `window` of the first entry matching the search string.
+///
+/// Keeps nothing if no entry matches.
+pub fn filter_around_match(har: &mut Har, search_str: &str, window: usize) {
+    let Some(anchor) = search_for::search_for(har, search_str)
+        .first()
+        .map(|result| result.request_num - 1)
+    else {
+        log::debug!("No entry matches {:?} to anchor the window", search_str);
+        har.log.entries.clear();
+        return;
+    };
+
+    filter_by_index(
+        har,
+        IndexRange {
+            start: Some(anchor.saturating_sub(window)),
+            end: Some(anchor + window + 1),
+        },
+    );
+}
+
+pub fn filter_by_phase(har: &mut Har, threshold: PhaseThreshold) {
+    har.log.entries.retain(|entry| {
+        entry
+            .timings
+            .as_ref()
+            .and_then(|timing| threshold.phase.value(timing))
+            .is_some_and(|value| value > threshold.threshold)
+    });
+}
@@ -0,0 +1,80 @@
+use crate::Har;
+use json::JsonValue;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::decode;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct JsonMatch {
+    pub request_num: usize,
+    pub url: String,
+    /// Which body the key was found in, `request` or `response`.
+    pub body: &'static str,
+    /// Path to the key within the body, i.e. `data.items[0].id`.
+    pub path: String,
+    pub value: String,
+}
+
+pub fn find_json(har: &Har, key: &str) -> Vec<JsonMatch> {
+    let mut matches = Vec::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let request_body = entry.request.post_data.as_ref().map(|p| p.text.clone());
+        let response_body = entry
+            .response
+            .content
+            .as_ref()
+            .and_then(decode::decode_body_text);
+
+        for (body, text) in [("request", request_body), ("response", response_body)] {
+            // only structured bodies are worth searching
+            let Some(value) = text
+                .and_then(|t| json::parse(&t).ok())
+                .filter(|v| v.is_object() || v.is_array())
+            else {
+                log::trace!("Skipping non-JSON {} body of request {}", body, i + 1);
+                continue;
+            };
+
+            let mut found = Vec::new();
+            find_key(&value, key, String::new(), &mut found);
+
+            matches.extend(found.into_iter().map(|(path, value)| JsonMatch {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                body,
+                path,
+                value,
+            }));
+        }
+    }
+
+    matches
+}
+
+fn find_key(value: &JsonValue, key: &str, path: String, found: &mut Vec<(String, String)>) {
+    match value {
+        JsonValue::Object(object) => {
+            for (name, child) in object.iter() {
+                let child_path = if path.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+
+                if name == key {
+                    found.push((child_path.clone(), child.dump()));
+                }
+
+                find_key(child, key, child_path, found);
+            }
+        }
+        JsonValue::Array(array) => {
+            for (i, child) in array.iter().enumerate() {
+                find_key(child, key, format!("{}[{}]", path, i), found);
+            }
+        }
+        _ => {}
+    }
+}
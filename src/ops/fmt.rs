@@ -0,0 +1,67 @@
+use colored::{ColoredString, Colorize};
+
+const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+/// Format a number of bytes using the largest fitting unit, i.e. `1.5 MB`.
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Format a duration in milliseconds, switching to seconds past one second.
+pub fn format_duration_ms(ms: f64) -> String {
+    if ms.abs() >= 1000.0 {
+        format!("{:.2} s", ms / 1000.0)
+    } else {
+        format!("{:.1} ms", ms)
+    }
+}
+
+/// Colour an HTTP status code by class, green for success, yellow for
+/// redirects and red for errors or requests that got no response.
+///
+/// Whether colour is actually written is left to the global `colored` setting.
+pub fn status(status: u16) -> ColoredString {
+    let text = status.to_string();
+    match status {
+        200..=299 => text.green(),
+        300..=399 => text.yellow(),
+        0 | 400..=599 => text.red(),
+        _ => text.normal(),
+    }
+}
+
+/// Formats sizes and durations, or prints exact numbers for scripting when raw.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Units {
+    pub raw: bool,
+}
+
+impl Units {
+    pub fn bytes(&self, bytes: u64) -> String {
+        if self.raw {
+            bytes.to_string()
+        } else {
+            format_bytes(bytes)
+        }
+    }
+
+    pub fn duration_ms(&self, ms: f64) -> String {
+        if self.raw {
+            ms.to_string()
+        } else {
+            format_duration_ms(ms)
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::har::Har;
+use std::collections::HashMap;
+use std::fmt::Write;
+use url::Url;
+
+use super::bytes;
+
+/// What host nodes are scaled by.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum NodeSize {
+    /// Number of requests made to the host.
+    Requests,
+
+    /// Bytes transferred from the host.
+    Bytes,
+}
+
+#[derive(Default)]
+struct HostNode {
+    requests: u64,
+    bytes: u64,
+}
+
+/// Render the capture as a Graphviz DOT graph.
+///
+/// Pages link to the resources loaded under them via `pageref`, and each
+/// resource links to the host it was fetched from.
+pub fn graph(har: &Har, size: NodeSize) -> String {
+    let mut dot =
+        String::from("digraph har {\n    rankdir=LR;\n    node [fontname=\"Helvetica\"];\n\n");
+
+    let pages = har.log.pages.as_deref().unwrap_or_default();
+    for page in pages {
+        let label = if page.title.is_empty() {
+            &page.id
+        } else {
+            &page.title
+        };
+        let _ = writeln!(
+            dot,
+            "    \"page:{}\" [label=\"{}\", shape=box, style=filled, fillcolor=lightblue];",
+            escape(&page.id),
+            escape(label)
+        );
+    }
+    if !pages.is_empty() {
+        dot.push('\n');
+    }
+
+    // hosts are kept in order of first appearance
+    let mut hosts: Vec<String> = Vec::new();
+    let mut host_nodes: HashMap<String, HostNode> = HashMap::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let parsed = Url::parse(&entry.request.url).ok();
+        let host = parsed
+            .as_ref()
+            .and_then(|url| url.host_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let path = parsed
+            .as_ref()
+            .map_or(entry.request.url.as_str(), |url| url.path());
+
+        let resource = format!("request:{}", i + 1);
+        let _ = writeln!(
+            dot,
+            "    \"{}\" [label=\"{} {}\", shape=ellipse];",
+            resource,
+            escape(&entry.request.method),
+            escape(path)
+        );
+
+        if let Some(pageref) = &entry.pageref {
+            let _ = writeln!(dot, "    \"page:{}\" -> \"{}\";", escape(pageref), resource);
+        }
+        let _ = writeln!(dot, "    \"{}\" -> \"host:{}\";", resource, escape(&host));
+
+        let node = host_nodes.entry(host.clone()).or_insert_with(|| {
+            hosts.push(host);
+            HostNode::default()
+        });
+        node.requests += 1;
+        node.bytes += bytes::transferred_bytes(entry).unwrap_or(0);
+    }
+
+    let largest = host_nodes
+        .values()
+        .map(|node| weight(node, size))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    if !hosts.is_empty() {
+        dot.push('\n');
+    }
+    for host in &hosts {
+        let node = &host_nodes[host];
+
+        // scale by area so large hosts don't swamp the graph
+        let scale = (weight(node, size) as f64 / largest as f64).sqrt();
+        let _ = writeln!(
+            dot,
+            "    \"host:{}\" [label=\"{}\\n{} requests, {} bytes\", shape=circle, style=filled, fillcolor=lightyellow, width={:.2}, fixedsize=true];",
+            escape(host),
+            escape(host),
+            node.requests,
+            node.bytes,
+            0.75 + 2.25 * scale
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn weight(node: &HostNode, size: NodeSize) -> u64 {
+    match size {
+        NodeSize::Requests => node.requests,
+        NodeSize::Bytes => node.bytes,
+    }
+}
+
+/// Escape a string for use inside a quoted DOT identifier.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
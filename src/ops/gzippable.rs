@@ -0,0 +1,50 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::{bytes, mime};
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Uncompressed {
+    pub request_num: usize,
+    pub url: String,
+    pub mime_type: String,
+    /// Decoded size of the body in bytes.
+    pub size: u64,
+}
+
+/// Find textual responses of at least `min_size` bytes sent without a
+/// `Content-Encoding`, or with only `identity`.
+pub fn find_gzippable(har: &Har, min_size: u64) -> Vec<Uncompressed> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let response = &entry.response;
+            let mime_type = mime::normalise(response.content.as_ref()?.mime_type.as_deref()?);
+            if !mime::is_textual(&mime_type) {
+                return None;
+            }
+
+            let size = bytes::decoded_bytes(entry).filter(|&size| size >= min_size)?;
+
+            let compressed = response
+                .headers
+                .iter()
+                .filter(|h| h.name.eq_ignore_ascii_case("content-encoding"))
+                .flat_map(|h| h.value.split(','))
+                .any(|coding| {
+                    let coding = coding.trim();
+                    !coding.is_empty() && !coding.eq_ignore_ascii_case("identity")
+                });
+
+            (!compressed).then(|| Uncompressed {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                mime_type,
+                size,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,99 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, fmt::Write};
+
+use super::decode;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct BodyHash {
+    pub request_num: usize,
+    pub url: String,
+    /// Hex encoded SHA-256 of the decoded body.
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DuplicateBody {
+    pub hash: String,
+    pub size: u64,
+    /// The distinct URLs serving the body, in the order first requested.
+    pub urls: Vec<String>,
+    pub request_nums: Vec<usize>,
+}
+
+impl DuplicateBody {
+    /// Bytes downloaded again after the first copy.
+    pub fn redundant_bytes(&self) -> u64 {
+        self.size * (self.request_nums.len() as u64 - 1)
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+/// Hash each response body, decoding base64 first. Empty bodies and those
+/// that don't decode are skipped.
+pub fn body_hashes(har: &Har) -> Vec<BodyHash> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let body = entry
+                .response
+                .content
+                .as_ref()
+                .and_then(decode::decode_body)
+                .filter(|body| !body.is_empty())?;
+
+            Some(BodyHash {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                hash: sha256_hex(&body),
+                size: body.len() as u64,
+            })
+        })
+        .collect()
+}
+
+/// Group bodies served from more than one URL, most redundant bytes first.
+/// The same URL fetched again is a caching problem rather than a duplicate, so
+/// it takes at least two URLs.
+pub fn duplicates(hashes: &[BodyHash]) -> Vec<DuplicateBody> {
+    let mut by_hash: HashMap<&str, DuplicateBody> = HashMap::new();
+
+    for body in hashes {
+        let duplicate = by_hash
+            .entry(body.hash.as_str())
+            .or_insert_with(|| DuplicateBody {
+                hash: body.hash.clone(),
+                size: body.size,
+                urls: Vec::new(),
+                request_nums: Vec::new(),
+            });
+        if !duplicate.urls.contains(&body.url) {
+            duplicate.urls.push(body.url.clone());
+        }
+        duplicate.request_nums.push(body.request_num);
+    }
+
+    let mut duplicates: Vec<DuplicateBody> = by_hash
+        .into_values()
+        .filter(|duplicate| duplicate.urls.len() > 1)
+        .collect();
+    duplicates.sort_by(|a, b| {
+        b.redundant_bytes()
+            .cmp(&a.redundant_bytes())
+            .then_with(|| a.request_nums.cmp(&b.request_nums))
+    });
+    duplicates
+}
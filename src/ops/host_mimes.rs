@@ -0,0 +1,73 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+use super::{counts, mime};
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct MimeCount {
+    pub mime_type: String,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct HostMimes {
+    pub host: String,
+    pub requests: usize,
+    pub mime_types: Vec<MimeCount>,
+}
+
+/// Count the distinct response mime types served by each host.
+///
+/// Hosts are ordered by number of requests, and mime types within each host
+/// by count.
+pub fn host_mimes(har: &Har) -> Vec<HostMimes> {
+    let mut hosts: HashMap<String, HashMap<String, usize>> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let Ok(url) = Url::parse(&entry.request.url) else {
+            continue;
+        };
+        let Some(host) = url.host_str() else {
+            continue;
+        };
+
+        let mime_type = entry
+            .response
+            .content
+            .as_ref()
+            .and_then(|c| c.mime_type.as_deref())
+            .map(mime::normalise)
+            .filter(|m| !m.is_empty())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        *hosts
+            .entry(host.to_string())
+            .or_default()
+            .entry(mime_type)
+            .or_insert(0) += 1;
+    }
+
+    let mut results: Vec<HostMimes> = hosts
+        .into_iter()
+        .map(|(host, mime_counts)| HostMimes {
+            host,
+            requests: mime_counts.values().sum(),
+            mime_types: counts::by_frequency(&mime_counts)
+                .into_iter()
+                .map(|(mime_type, count)| MimeCount {
+                    mime_type: mime_type.clone(),
+                    count,
+                })
+                .collect(),
+        })
+        .collect();
+    results.sort_by(|a, b| {
+        b.requests
+            .cmp(&a.requests)
+            .then_with(|| a.host.cmp(&b.host))
+    });
+    results
+}
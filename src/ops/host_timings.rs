@@ -0,0 +1,48 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+use super::stats::Stats;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct HostTiming {
+    pub host: String,
+    pub stats: Stats,
+}
+
+pub fn host_timings(har: &Har) -> Vec<HostTiming> {
+    let mut times: HashMap<String, Vec<f64>> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let Some(host) = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+        else {
+            continue;
+        };
+
+        times.entry(host).or_default().push(entry.time);
+    }
+
+    let mut results: Vec<HostTiming> = times
+        .into_iter()
+        .filter_map(|(host, values)| {
+            Some(HostTiming {
+                host,
+                stats: Stats::from_values(&values)?,
+            })
+        })
+        .collect();
+
+    // slowest hosts first
+    results.sort_by(|a, b| {
+        b.stats
+            .total
+            .total_cmp(&a.stats.total)
+            .then_with(|| a.host.cmp(&b.host))
+    });
+
+    results
+}
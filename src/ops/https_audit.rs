@@ -0,0 +1,44 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashSet;
+use url::Url;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct InsecureRequest {
+    pub request_num: usize,
+    pub url: String,
+    pub host: String,
+}
+
+/// Find `http://` requests to hosts that serve something over `https://`
+/// elsewhere in the capture, so could have used HTTPS too.
+pub fn https_audit(har: &Har) -> Vec<InsecureRequest> {
+    let urls: Vec<Option<Url>> = har
+        .log
+        .entries
+        .iter()
+        .map(|entry| Url::parse(&entry.request.url).ok())
+        .collect();
+
+    let secure_hosts: HashSet<&str> = urls
+        .iter()
+        .flatten()
+        .filter(|url| url.scheme() == "https")
+        .filter_map(|url| url.host_str())
+        .collect();
+
+    urls.iter()
+        .enumerate()
+        .filter_map(|(i, url)| {
+            let url = url.as_ref().filter(|url| url.scheme() == "http")?;
+            let host = url.host_str().filter(|host| secure_hosts.contains(host))?;
+
+            Some(InsecureRequest {
+                request_num: i + 1,
+                url: url.to_string(),
+                host: host.to_string(),
+            })
+        })
+        .collect()
+}
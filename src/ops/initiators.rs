@@ -0,0 +1,107 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct InitiatorCount {
+    /// The initiator type, i.e. `parser`, `script` or `preload`.
+    pub kind: String,
+    pub requests: usize,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ScriptCount {
+    pub url: String,
+    pub requests: usize,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct Initiators {
+    /// Entries without a Chrome `_initiator` object, left out of the counts.
+    pub skipped: usize,
+    /// Most requests first.
+    pub kinds: Vec<InitiatorCount>,
+    /// The scripts triggering the most requests, for script initiators.
+    pub scripts: Vec<ScriptCount>,
+}
+
+/// URL of the script nearest the top of a script initiator's stack, following
+/// the async parents if the frames have none, i.e. for a `setTimeout` callback.
+fn script_url(initiator: &Value) -> Option<&str> {
+    let mut stack = initiator.get("stack");
+
+    while let Some(current) = stack {
+        let url = current
+            .get("callFrames")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|frame| frame.get("url").and_then(Value::as_str))
+            .find(|url| !url.is_empty());
+        if url.is_some() {
+            return url;
+        }
+        stack = current.get("parent");
+    }
+
+    initiator
+        .get("url")
+        .and_then(Value::as_str)
+        .filter(|url| !url.is_empty())
+}
+
+/// Tally what triggered each request from Chrome's `_initiator` extension,
+/// keeping the `top` scripts.
+pub fn initiators(har: &Har, top: usize) -> Initiators {
+    let mut kinds: HashMap<&str, usize> = HashMap::new();
+    let mut scripts: HashMap<&str, usize> = HashMap::new();
+    let mut skipped = 0;
+
+    for entry in &har.log.entries {
+        let Some((initiator, kind)) = entry.initiator.as_ref().and_then(|initiator| {
+            let kind = initiator.get("type")?.as_str()?;
+            Some((initiator, kind))
+        }) else {
+            skipped += 1;
+            continue;
+        };
+
+        *kinds.entry(kind).or_default() += 1;
+        if kind == "script" {
+            if let Some(url) = script_url(initiator) {
+                *scripts.entry(url).or_default() += 1;
+            }
+        }
+    }
+
+    let mut kinds: Vec<InitiatorCount> = kinds
+        .into_iter()
+        .map(|(kind, requests)| InitiatorCount {
+            kind: kind.to_string(),
+            requests,
+        })
+        .collect();
+    kinds.sort_by(|a, b| {
+        b.requests
+            .cmp(&a.requests)
+            .then_with(|| a.kind.cmp(&b.kind))
+    });
+
+    let mut scripts: Vec<ScriptCount> = scripts
+        .into_iter()
+        .map(|(url, requests)| ScriptCount {
+            url: url.to_string(),
+            requests,
+        })
+        .collect();
+    scripts.sort_by(|a, b| b.requests.cmp(&a.requests).then_with(|| a.url.cmp(&b.url)));
+    scripts.truncate(top);
+
+    Initiators {
+        skipped,
+        kinds,
+        scripts,
+    }
+}
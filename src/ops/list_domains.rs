@@ -1,20 +1,60 @@
-use std::collections::HashSet;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 
 use crate::Har;
 use url::Url;
 
+/// The host of a request URL, data: and blob: URLs have none.
+fn host(url_str: &str) -> Option<String> {
+    Url::parse(url_str)
+        .ok()?
+        .host_str()
+        .map(|host| host.to_string())
+}
+
 pub fn list_domains(har: &Har) -> Vec<String> {
-    let mut urls: HashSet<String> = HashSet::new();
-    for entry in &har.log.entries {
-        let url_str = &entry.request.url;
+    let urls: HashSet<String> = har
+        .log
+        .entries
+        .iter()
+        .filter_map(|entry| host(&entry.request.url))
+        .collect();
+
+    urls.into_iter().collect()
+}
+
+/// Sort key putting subdomains next to their parent, the name reversed.
+fn reversed(domain: &str) -> String {
+    domain.chars().rev().collect()
+}
+
+/// The unique domains sorted by their reversed name, so subdomains sit together.
+pub fn sorted_domains(har: &Har) -> Vec<String> {
+    let mut domains = list_domains(har);
+    domains.sort_by_cached_key(|domain| reversed(domain));
+    domains
+}
 
-        if let Some(host) = match Url::parse(url_str) {
-            Ok(parsed_url) => parsed_url.host_str().map(|x| x.to_string()),
-            Err(_) => continue,
-        } {
-            urls.insert(host);
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct DomainCount {
+    pub domain: String,
+    pub requests: usize,
+}
+
+/// Count the requests to each domain, sorted by reversed name.
+pub fn domain_counts(har: &Har) -> Vec<DomainCount> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in &har.log.entries {
+        if let Some(host) = host(&entry.request.url) {
+            *counts.entry(host).or_default() += 1;
         }
     }
 
-    urls.into_iter().collect()
+    let mut counts: Vec<DomainCount> = counts
+        .into_iter()
+        .map(|(domain, requests)| DomainCount { domain, requests })
+        .collect();
+    counts.sort_by_cached_key(|count| reversed(&count.domain));
+    counts
 }
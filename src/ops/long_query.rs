@@ -0,0 +1,37 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use url::Url;
+
+use super::query_params;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct LongQuery {
+    pub request_num: usize,
+    pub url: String,
+    pub param_count: usize,
+    /// Length of the query string in bytes, excluding the `?`.
+    pub query_length: usize,
+}
+
+/// Find URLs with a query string longer than `max_length` bytes or with
+/// more than `max_params` parameters.
+pub fn find_long_queries(har: &Har, max_length: usize, max_params: usize) -> Vec<LongQuery> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let url = &entry.request.url;
+            let query_length = Url::parse(url).ok()?.query()?.len();
+            let param_count = query_params::parse_query(url)?.len();
+
+            (query_length > max_length || param_count > max_params).then(|| LongQuery {
+                request_num: i + 1,
+                url: url.clone(),
+                param_count,
+                query_length,
+            })
+        })
+        .collect()
+}
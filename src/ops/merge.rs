@@ -0,0 +1,100 @@
+use std::collections::{HashMap, HashSet};
+
+use json::JsonValue;
+
+use crate::Har;
+
+/// Merge captures into one, keeping the first's creator and adding the pages
+/// and entries of the rest after its own, in the order given.
+///
+/// Tools number pages from `page_1` in every capture, so a page id already
+/// taken is prefixed with the name of its capture, i.e. `b.har:page_1`, and
+/// its entries updated to match. `None` if there are no captures.
+pub fn merge<I>(hars: I) -> Option<Har>
+where
+    I: IntoIterator<Item = (String, Har)>,
+{
+    let mut hars = hars.into_iter();
+    let (_, mut merged) = hars.next()?;
+
+    let mut page_ids: HashSet<String> = merged
+        .log
+        .pages
+        .iter()
+        .flatten()
+        .map(|page| page.id.clone())
+        .collect();
+
+    for (name, har) in hars {
+        let mut renamed: HashMap<String, String> = HashMap::new();
+
+        for mut page in har.log.pages.into_iter().flatten() {
+            if page_ids.contains(&page.id) {
+                let id = format!("{}:{}", name, page.id);
+                log::debug!("Renaming page {} from {} to {}", page.id, name, id);
+                renamed.insert(std::mem::replace(&mut page.id, id.clone()), id);
+            }
+            page_ids.insert(page.id.clone());
+            merged.log.pages.get_or_insert_with(Vec::new).push(page);
+        }
+
+        for mut entry in har.log.entries {
+            if let Some(id) = entry.pageref.as_ref().and_then(|id| renamed.get(id)) {
+                entry.pageref = Some(id.clone());
+            }
+            merged.log.entries.push(entry);
+        }
+    }
+
+    Some(merged)
+}
+
+/// Merge raw captures the same way as [`merge`], keeping every field of the
+/// documents as written, including ones the [`Har`] model doesn't know.
+pub fn merge_raw<I>(docs: I) -> Option<JsonValue>
+where
+    I: IntoIterator<Item = (String, JsonValue)>,
+{
+    let mut docs = docs.into_iter();
+    let (_, mut merged) = docs.next()?;
+
+    let mut page_ids: HashSet<String> = merged["log"]["pages"]
+        .members()
+        .filter_map(|page| page["id"].as_str())
+        .map(str::to_string)
+        .collect();
+
+    for (name, mut doc) in docs {
+        let mut renamed: HashMap<String, String> = HashMap::new();
+
+        for page in doc["log"]["pages"].members_mut() {
+            let Some(old) = page["id"].as_str().map(str::to_string) else {
+                continue;
+            };
+            if page_ids.contains(&old) {
+                let id = format!("{}:{}", name, old);
+                log::debug!("Renaming page {} from {} to {}", old, name, id);
+                page["id"] = id.clone().into();
+                renamed.insert(old, id.clone());
+                page_ids.insert(id);
+            } else {
+                page_ids.insert(old);
+            }
+
+            if !merged["log"]["pages"].is_array() {
+                merged["log"]["pages"] = JsonValue::new_array();
+            }
+            // pushing onto an array can't fail
+            let _ = merged["log"]["pages"].push(page.take());
+        }
+
+        for entry in doc["log"]["entries"].members_mut() {
+            if let Some(id) = entry["pageref"].as_str().and_then(|id| renamed.get(id)) {
+                entry["pageref"] = id.clone().into();
+            }
+            let _ = merged["log"]["entries"].push(entry.take());
+        }
+    }
+
+    Some(merged)
+}
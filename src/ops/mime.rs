@@ -0,0 +1,45 @@
+/// Normalise a mime type by lowercasing it and stripping any parameters.
+///
+/// i.e. `Text/HTML; charset=utf-8` becomes `text/html`.
+pub fn normalise(mime_type: &str) -> String {
+    mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Check if a normalised mime type holds text, and so would benefit from compression.
+pub fn is_textual(mime_type: &str) -> bool {
+    mime_type.starts_with("text/")
+        || mime_type.ends_with("+json")
+        || mime_type.ends_with("+xml")
+        || matches!(
+            mime_type,
+            "application/json"
+                | "application/javascript"
+                | "application/x-javascript"
+                | "application/ecmascript"
+                | "application/xml"
+                | "application/manifest+json"
+                | "image/svg+xml"
+        )
+}
+
+/// Check if a normalised mime type is a static asset that's normally cacheable,
+/// i.e. images, fonts, stylesheets and scripts.
+pub fn is_static_asset(mime_type: &str) -> bool {
+    mime_type.starts_with("image/")
+        || mime_type.starts_with("font/")
+        || mime_type.starts_with("application/font-")
+        || matches!(
+            mime_type,
+            "text/css"
+                | "text/javascript"
+                | "application/javascript"
+                | "application/x-javascript"
+                | "application/ecmascript"
+                | "application/vnd.ms-fontobject"
+        )
+}
@@ -0,0 +1,92 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use url::Url;
+
+use super::mime;
+
+// extensions that strongly imply a type, with the mime types accepted for them
+const EXTENSIONS: [(&str, &[&str]); 19] = [
+    (
+        "js",
+        &[
+            "application/javascript",
+            "text/javascript",
+            "application/x-javascript",
+            "application/ecmascript",
+        ],
+    ),
+    ("mjs", &["application/javascript", "text/javascript"]),
+    ("css", &["text/css"]),
+    ("json", &["application/json"]),
+    ("html", &["text/html"]),
+    ("htm", &["text/html"]),
+    ("png", &["image/png"]),
+    ("jpg", &["image/jpeg"]),
+    ("jpeg", &["image/jpeg"]),
+    ("gif", &["image/gif"]),
+    ("webp", &["image/webp"]),
+    ("svg", &["image/svg+xml"]),
+    ("ico", &["image/x-icon", "image/vnd.microsoft.icon"]),
+    ("woff", &["font/woff", "application/font-woff"]),
+    ("woff2", &["font/woff2", "application/font-woff2"]),
+    ("ttf", &["font/ttf", "application/x-font-ttf"]),
+    ("wasm", &["application/wasm"]),
+    ("mp4", &["video/mp4"]),
+    ("pdf", &["application/pdf"]),
+];
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct MimeMismatch {
+    pub request_num: usize,
+    pub url: String,
+    pub expected: String,
+    pub actual: String,
+    pub status: u16,
+}
+
+pub fn find_mismatches(har: &Har) -> Vec<MimeMismatch> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let response = &entry.response;
+
+            // redirects and not modified responses don't carry the asset
+            if (300..400).contains(&response.status) {
+                return None;
+            }
+
+            let actual = mime::normalise(response.content.as_ref()?.mime_type.as_deref()?);
+            if actual.is_empty() {
+                return None;
+            }
+
+            let accepted = expected_types(&entry.request.url)?;
+            if accepted.contains(&actual.as_str()) {
+                return None;
+            }
+
+            Some(MimeMismatch {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                expected: accepted[0].to_string(),
+                actual,
+                status: response.status,
+            })
+        })
+        .collect()
+}
+
+fn expected_types(url_str: &str) -> Option<&'static [&'static str]> {
+    let url = Url::parse(url_str).ok()?;
+    let file_name = url.path_segments()?.next_back()?;
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let extension = extension.to_ascii_lowercase();
+
+    EXTENSIONS
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map(|(_, types)| *types)
+}
@@ -1,8 +1,67 @@
+pub mod annotate;
+pub mod bloat;
 pub mod blocklist;
+pub mod body;
+pub mod bottleneck;
+pub mod budget;
+pub mod bytes;
+pub mod cache_control;
+pub mod chunked;
+pub mod comments;
+pub mod compression;
+pub mod connections;
+pub mod cookie_audit;
+pub mod cookie_flow;
+pub mod cookie_stats;
+pub mod cookies;
+pub mod cors;
 pub mod count_requests;
 pub mod count_schemes;
 pub mod count_urls;
+pub mod counts;
+pub mod critical_path;
+pub mod decode;
+pub mod devtools_summary;
 pub mod dns;
+pub mod dns_timings;
+pub mod domain_lists;
+pub mod duplicate_headers;
+pub mod export_postman;
 pub mod filter;
+pub mod find_json;
+pub mod fmt;
+pub mod graph;
+pub mod gzippable;
+pub mod hashes;
+pub mod host_mimes;
+pub mod host_timings;
+pub mod https_audit;
+pub mod initiators;
 pub mod list_domains;
+pub mod long_query;
+pub mod merge;
+pub mod mime;
+pub mod mime_mismatch;
+pub mod no_cache;
+pub mod pages;
+pub mod ports;
+pub mod private_ips;
+pub mod query_params;
+pub mod rate;
+pub mod rate_limit;
+pub mod redact;
+pub mod redirects;
+pub mod retries;
 pub mod search_for;
+pub mod serialization;
+pub mod size_ratio;
+pub mod soft_errors;
+pub mod stats;
+pub mod third_party;
+pub mod timing;
+pub mod ttfb;
+pub mod url_check;
+pub mod urls;
+pub mod user_agents;
+pub mod validate;
+pub mod where_param;
@@ -0,0 +1,41 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::mime;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct UncachedAsset {
+    pub request_num: usize,
+    pub url: String,
+    pub mime_type: String,
+    pub status: u16,
+}
+
+/// Find static assets served without a `Cache-Control` or `Expires` header.
+pub fn uncached_assets(har: &Har) -> Vec<UncachedAsset> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let response = &entry.response;
+            let mime_type = mime::normalise(response.content.as_ref()?.mime_type.as_deref()?);
+            if !mime::is_static_asset(&mime_type) {
+                return None;
+            }
+
+            let has_caching = response.headers.iter().any(|h| {
+                h.name.eq_ignore_ascii_case("cache-control")
+                    || h.name.eq_ignore_ascii_case("expires")
+            });
+
+            (!has_caching).then(|| UncachedAsset {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                mime_type,
+                status: response.status,
+            })
+        })
+        .collect()
+}
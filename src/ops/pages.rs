@@ -0,0 +1,75 @@
+use json::JsonValue;
+
+/// A run of entries from one page after grouping.
+#[derive(Clone, Debug)]
+pub struct PageGroup {
+    /// `None` for entries that aren't part of a page.
+    pub pageref: Option<String>,
+    pub title: Option<String>,
+    pub entries: usize,
+}
+
+/// Reorder the entries of a raw HAR so each page's entries are together.
+///
+/// Pages are in the order they're listed in `log.pages`, followed by pages
+/// only referenced by entries, then entries without a page. Entries keep
+/// their relative order within each page and none are dropped.
+pub fn group_by_page(har: &mut JsonValue) -> Vec<PageGroup> {
+    let mut order: Vec<Option<String>> = har["log"]["pages"]
+        .members()
+        .filter_map(|page| page["id"].as_str())
+        .map(|id| Some(id.to_string()))
+        .collect();
+
+    let entries: Vec<JsonValue> = har["log"]["entries"]
+        .members_mut()
+        .map(JsonValue::take)
+        .collect();
+    let mut groups: Vec<Vec<JsonValue>> = vec![Vec::new(); order.len()];
+
+    for entry in entries {
+        let pageref = entry["pageref"].as_str().map(String::from);
+        let index = match order.iter().position(|p| *p == pageref) {
+            Some(index) => index,
+            None => {
+                order.push(pageref);
+                groups.push(Vec::new());
+                order.len() - 1
+            }
+        };
+        groups[index].push(entry);
+    }
+
+    // entries without a page go last
+    if let Some(index) = order.iter().position(Option::is_none) {
+        let pageref = order.remove(index);
+        order.push(pageref);
+        let group = groups.remove(index);
+        groups.push(group);
+    }
+
+    let mut summary = Vec::new();
+    let mut grouped = JsonValue::new_array();
+    for (pageref, group) in order.into_iter().zip(groups) {
+        let title = pageref.as_ref().and_then(|id| {
+            har["log"]["pages"]
+                .members()
+                .find(|page| page["id"] == id.as_str())
+                .and_then(|page| page["title"].as_str())
+                .map(String::from)
+        });
+        summary.push(PageGroup {
+            pageref,
+            title,
+            entries: group.len(),
+        });
+
+        for entry in group {
+            // pushing into an array can't fail
+            let _ = grouped.push(entry);
+        }
+    }
+    har["log"]["entries"] = grouped;
+
+    summary
+}
@@ -0,0 +1,46 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+const STANDARD_PORTS: [u16; 2] = [80, 443];
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PortUsage {
+    pub host: String,
+    pub port: u16,
+    pub count: usize,
+}
+
+/// Find requests to ports other than 80 and 443, grouped by host and port.
+///
+/// Ports implied by the scheme are used when the URL doesn't give one.
+pub fn non_standard_ports(har: &Har) -> Vec<PortUsage> {
+    let mut counts: HashMap<(String, u16), usize> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let Ok(url) = Url::parse(&entry.request.url) else {
+            continue;
+        };
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            continue;
+        };
+
+        if !STANDARD_PORTS.contains(&port) {
+            *counts.entry((host.to_string(), port)).or_insert(0) += 1;
+        }
+    }
+
+    let mut usages: Vec<PortUsage> = counts
+        .into_iter()
+        .map(|((host, port), count)| PortUsage { host, port, count })
+        .collect();
+    usages.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.host.cmp(&b.host))
+            .then_with(|| a.port.cmp(&b.port))
+    });
+    usages
+}
@@ -0,0 +1,98 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    fmt,
+    net::{IpAddr, Ipv6Addr},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IpRange {
+    /// RFC1918 private IPv4 ranges.
+    Private,
+    /// RFC4193 unique local IPv6 addresses.
+    UniqueLocal,
+    Loopback,
+    LinkLocal,
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IpRange::Private => "private (RFC1918)",
+            IpRange::UniqueLocal => "unique local (RFC4193)",
+            IpRange::Loopback => "loopback",
+            IpRange::LinkLocal => "link-local",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PrivateIpResult {
+    pub request_num: usize,
+    pub url: String,
+    pub ip: IpAddr,
+    pub range: IpRange,
+}
+
+pub fn find_private_ips(har: &Har) -> Vec<PrivateIpResult> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let ip = parse_ip(entry.server_ip_address.as_deref()?)?;
+            let range = classify(ip)?;
+
+            Some(PrivateIpResult {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                ip,
+                range,
+            })
+        })
+        .collect()
+}
+
+fn parse_ip(ip_str: &str) -> Option<IpAddr> {
+    // some tools write IPv6 addresses in brackets
+    ip_str
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+pub fn classify(ip: IpAddr) -> Option<IpRange> {
+    match ip {
+        IpAddr::V4(v4) => {
+            if v4.is_loopback() {
+                Some(IpRange::Loopback)
+            } else if v4.is_private() {
+                Some(IpRange::Private)
+            } else if v4.is_link_local() {
+                Some(IpRange::LinkLocal)
+            } else {
+                None
+            }
+        }
+        IpAddr::V6(v6) => {
+            // treat IPv4-mapped addresses as their IPv4 equivalent
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return classify(IpAddr::V4(v4));
+            }
+
+            if v6 == Ipv6Addr::LOCALHOST {
+                Some(IpRange::Loopback)
+            } else if v6.segments()[0] & 0xfe00 == 0xfc00 {
+                Some(IpRange::UniqueLocal)
+            } else if v6.segments()[0] & 0xffc0 == 0xfe80 {
+                Some(IpRange::LinkLocal)
+            } else {
+                None
+            }
+        }
+    }
+}
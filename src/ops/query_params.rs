@@ -0,0 +1,13 @@
+use url::Url;
+
+/// Parse the query string of a URL into decoded name/value pairs.
+pub fn parse_query(url_str: &str) -> Option<Vec<(String, String)>> {
+    let parsed = Url::parse(url_str).ok()?;
+
+    Some(
+        parsed
+            .query_pairs()
+            .map(|(name, value)| (name.into_owned(), value.into_owned()))
+            .collect(),
+    )
+}
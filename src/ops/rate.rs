@@ -0,0 +1,98 @@
+use crate::Har;
+use chrono::{DateTime, TimeDelta, Utc};
+use std::{collections::BTreeMap, str::FromStr};
+
+// past this many buckets, gaps are left out rather than printed as zeros
+const MAX_FILLED_BUCKETS: i64 = 1000;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// A bucket width, like `500ms`, `1s`, `5m` or `1h`.
+#[derive(Debug, Clone, Copy)]
+pub struct Interval(pub TimeDelta);
+
+impl FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+
+        let number: i64 = number
+            .parse()
+            .map_err(|_| format!("invalid interval '{}', expected i.e. 1s or 1m", s))?;
+
+        let delta = match unit {
+            "ms" => TimeDelta::milliseconds(number),
+            "s" | "" => TimeDelta::seconds(number),
+            "m" => TimeDelta::minutes(number),
+            "h" => TimeDelta::hours(number),
+            _ => {
+                return Err(format!(
+                    "unknown interval unit '{}', expected ms, s, m or h",
+                    unit
+                ))
+            }
+        };
+
+        if delta <= TimeDelta::zero() {
+            return Err("interval must be greater than zero".to_string());
+        }
+
+        Ok(Interval(delta))
+    }
+}
+
+/// Count requests started in each interval, in time order.
+pub fn request_rate(har: &Har, interval: Interval) -> Vec<(DateTime<Utc>, usize)> {
+    let width = interval.0.num_milliseconds();
+
+    let mut buckets: BTreeMap<i64, usize> = BTreeMap::new();
+    for entry in &har.log.entries {
+        let Ok(start) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+            continue;
+        };
+        let bucket = start.timestamp_millis().div_euclid(width);
+        *buckets.entry(bucket).or_insert(0) += 1;
+    }
+
+    let (Some(&first), Some(&last)) = (buckets.keys().next(), buckets.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    // fill gaps with zero counts unless the capture spans too many intervals
+    let filled: Vec<(i64, usize)> = if last - first < MAX_FILLED_BUCKETS {
+        (first..=last)
+            .map(|b| (b, buckets.get(&b).copied().unwrap_or(0)))
+            .collect()
+    } else {
+        log::debug!(
+            "Capture spans {} intervals, leaving out empty ones",
+            last - first + 1
+        );
+        buckets.into_iter().collect()
+    };
+
+    filled
+        .into_iter()
+        .filter_map(|(bucket, count)| {
+            Some((DateTime::from_timestamp_millis(bucket * width)?, count))
+        })
+        .collect()
+}
+
+/// Render counts as a line of block characters scaled to the largest count.
+pub fn sparkline(counts: &[usize]) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    counts
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                ' '
+            } else {
+                SPARKS[(count * (SPARKS.len() - 1)).div_ceil(max)]
+            }
+        })
+        .collect()
+}
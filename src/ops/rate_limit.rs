@@ -0,0 +1,116 @@
+use crate::Har;
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+use url::Url;
+
+use super::rate::Interval;
+
+const TOO_MANY_REQUESTS: u16 = 429;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct HostRateLimit {
+    pub host: String,
+    pub requests: usize,
+    /// Request numbers of the 429 responses, in time order.
+    pub limited: Vec<usize>,
+    /// The lowest rate, in requests per second over the window, leading up to
+    /// a 429. The limit is at or below this.
+    pub limited_at_rps: f64,
+    /// The highest rate reached before the first 429 without being limited, the
+    /// limit is above this. `None` if the first request was limited.
+    pub allowed_up_to_rps: Option<f64>,
+    /// The `Retry-After` header of the first 429, if it sent one.
+    pub retry_after: Option<String>,
+}
+
+/// A request to a host, with its start in milliseconds since the epoch.
+struct Timed {
+    request_num: usize,
+    start: i64,
+    status: u16,
+}
+
+/// Find the hosts that answered with 429 Too Many Requests, and estimate the
+/// rate that set them off from the requests to the host in the `window` up to
+/// and including each one. Hosts that never rate limited are left out, the
+/// most limited come first.
+pub fn rate_limits(har: &Har, window: Interval) -> Vec<HostRateLimit> {
+    let width = window.0.num_milliseconds();
+    let seconds = width as f64 / 1000.0;
+
+    let mut hosts: HashMap<String, Vec<Timed>> = HashMap::new();
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let Ok(start) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+            log::debug!("Skipping request {} with an invalid startedDateTime", i + 1);
+            continue;
+        };
+        let Some(host) = Url::parse(&entry.request.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from))
+        else {
+            continue;
+        };
+
+        hosts.entry(host).or_default().push(Timed {
+            request_num: i + 1,
+            start: start.timestamp_millis(),
+            status: entry.response.status,
+        });
+    }
+
+    let mut results: Vec<HostRateLimit> = hosts
+        .into_iter()
+        .filter(|(_, requests)| requests.iter().any(|r| r.status == TOO_MANY_REQUESTS))
+        .map(|(host, mut requests)| {
+            requests.sort_by_key(|r| (r.start, r.request_num));
+
+            let mut limited = Vec::new();
+            let mut limited_at_rps = f64::INFINITY;
+            let mut allowed_up_to_rps: Option<f64> = None;
+
+            // requests[window_start..=i] started within the window ending at i
+            let mut window_start = 0;
+            for (i, request) in requests.iter().enumerate() {
+                while requests[window_start].start <= request.start - width {
+                    window_start += 1;
+                }
+                let rps = (i - window_start + 1) as f64 / seconds;
+
+                if request.status == TOO_MANY_REQUESTS {
+                    limited.push(request.request_num);
+                    limited_at_rps = limited_at_rps.min(rps);
+                } else if limited.is_empty() {
+                    allowed_up_to_rps = Some(allowed_up_to_rps.map_or(rps, |max| max.max(rps)));
+                }
+            }
+
+            let retry_after = limited.first().and_then(|&request_num| {
+                har.log.entries[request_num - 1]
+                    .response
+                    .headers
+                    .iter()
+                    .find(|h| h.name.eq_ignore_ascii_case("retry-after"))
+                    .map(|h| h.value.clone())
+            });
+
+            HostRateLimit {
+                host,
+                requests: requests.len(),
+                limited,
+                limited_at_rps,
+                allowed_up_to_rps,
+                retry_after,
+            }
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.limited
+            .len()
+            .cmp(&a.limited.len())
+            .then_with(|| a.host.cmp(&b.host))
+    });
+    results
+}
@@ -0,0 +1,189 @@
+use json::JsonValue;
+use url::form_urlencoded;
+
+/// Placeholder written over redacted values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose whole value is a credential.
+const SECRET_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "x-auth-token",
+];
+
+/// Headers holding a URL, or in the case of `:path` its path and query.
+const URL_HEADERS: [&str; 4] = [":path", "referer", "location", "content-location"];
+
+/// Query parameter names containing any of these are treated as secrets.
+const SECRET_PARAMS: [&str; 9] = [
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "apikey",
+    "api_key",
+    "auth",
+    "session",
+    "signature",
+];
+
+/// Strip credentials from a raw HAR, and the request and response bodies
+/// unless `keep_bodies` is set.
+pub fn redact(har: &mut JsonValue, keep_bodies: bool) {
+    for entry in har["log"]["entries"].members_mut() {
+        let request = &mut entry["request"];
+        redact_headers(&mut request["headers"]);
+        redact_cookie_array(&mut request["cookies"]);
+        redact_query(request);
+        if !keep_bodies {
+            redact_post_data(&mut request["postData"]);
+        }
+
+        let response = &mut entry["response"];
+        redact_headers(&mut response["headers"]);
+        redact_cookie_array(&mut response["cookies"]);
+        if let Some(url) = response["redirectURL"].as_str().and_then(redact_url) {
+            response["redirectURL"] = url.into();
+        }
+        if !keep_bodies {
+            let content = &mut response["content"];
+            if content.has_key("text") {
+                content["text"] = REDACTED.into();
+                content.remove("encoding");
+            }
+        }
+    }
+}
+
+fn is_secret_param(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_PARAMS.iter().any(|secret| name.contains(secret))
+}
+
+fn redact_headers(headers: &mut JsonValue) {
+    for header in headers.members_mut() {
+        let name = header["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let Some(value) = header["value"].as_str() else {
+            continue;
+        };
+
+        let redacted = match name.as_str() {
+            name if SECRET_HEADERS.contains(&name) => REDACTED.to_string(),
+            name if URL_HEADERS.contains(&name) => match redact_url(value) {
+                Some(url) => url,
+                None => continue,
+            },
+            "cookie" => redact_cookie_header(value),
+            "set-cookie" => value
+                .lines()
+                .map(redact_set_cookie)
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => continue,
+        };
+        header["value"] = redacted.into();
+    }
+}
+
+/// Redact the values of a `Cookie` header, keeping the names.
+fn redact_cookie_header(value: &str) -> String {
+    value
+        .split(';')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) => format!("{}={}", name.trim(), REDACTED),
+            None => pair.trim().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Redact the value of a `Set-Cookie` header, keeping the name and attributes.
+fn redact_set_cookie(value: &str) -> String {
+    let (pair, attributes) = value.split_once(';').unwrap_or((value, ""));
+    let Some((name, _)) = pair.split_once('=') else {
+        return value.to_string();
+    };
+
+    if attributes.is_empty() {
+        format!("{}={}", name.trim(), REDACTED)
+    } else {
+        format!("{}={};{}", name.trim(), REDACTED, attributes)
+    }
+}
+
+fn redact_cookie_array(cookies: &mut JsonValue) {
+    for cookie in cookies.members_mut() {
+        cookie["value"] = REDACTED.into();
+    }
+}
+
+fn redact_query(request: &mut JsonValue) {
+    for param in request["queryString"].members_mut() {
+        if is_secret_param(param["name"].as_str().unwrap_or_default()) {
+            param["value"] = REDACTED.into();
+        }
+    }
+
+    if let Some(url) = request["url"].as_str().and_then(redact_url) {
+        request["url"] = url.into();
+    }
+}
+
+/// Redact the values of secret query parameters in a URL, `None` if it has
+/// none.
+///
+/// The URL may be relative, as in `Location`, or only a path as in `:path`,
+/// so the query is found by hand and the other parameters left as written.
+fn redact_url(url: &str) -> Option<String> {
+    let (start, rest) = url.split_once('?')?;
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+
+    let placeholder: String = form_urlencoded::byte_serialize(REDACTED.as_bytes()).collect();
+    let mut redacted = false;
+    let pairs: Vec<String> = query
+        .split('&')
+        .map(|pair| {
+            let name = pair.split_once('=').map_or(pair, |(name, _)| name);
+            let decoded = form_urlencoded::parse(name.as_bytes())
+                .next()
+                .map(|(name, _)| name.into_owned())
+                .unwrap_or_default();
+            if is_secret_param(&decoded) {
+                redacted = true;
+                format!("{}={}", name, placeholder)
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect();
+    if !redacted {
+        return None;
+    }
+
+    let mut url = format!("{}?{}", start, pairs.join("&"));
+    if let Some(fragment) = fragment {
+        url.push('#');
+        url.push_str(fragment);
+    }
+    Some(url)
+}
+
+fn redact_post_data(post_data: &mut JsonValue) {
+    if post_data.is_null() {
+        return;
+    }
+
+    post_data["text"] = REDACTED.into();
+    for param in post_data["params"].members_mut() {
+        if param.has_key("value") {
+            param["value"] = REDACTED.into();
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use crate::har::{Entry, Har};
+use anyhow::Result;
+use reqwest::{redirect::Policy, Client};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{fmt, sync::Arc, time::Duration};
+use tokio::sync::Semaphore;
+use url::Url;
+
+use super::decode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RedirectKind {
+    MovedPermanently,
+    Found,
+    SeeOther,
+    TemporaryRedirect,
+    PermanentRedirect,
+    MetaRefresh,
+    Other(u16),
+}
+
+impl RedirectKind {
+    fn from_status(status: u16) -> Self {
+        match status {
+            301 => RedirectKind::MovedPermanently,
+            302 => RedirectKind::Found,
+            303 => RedirectKind::SeeOther,
+            307 => RedirectKind::TemporaryRedirect,
+            308 => RedirectKind::PermanentRedirect,
+            status => RedirectKind::Other(status),
+        }
+    }
+
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            RedirectKind::MovedPermanently | RedirectKind::PermanentRedirect
+        )
+    }
+}
+
+impl fmt::Display for RedirectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RedirectKind::MovedPermanently => write!(f, "301 Moved Permanently"),
+            RedirectKind::Found => write!(f, "302 Found"),
+            RedirectKind::SeeOther => write!(f, "303 See Other"),
+            RedirectKind::TemporaryRedirect => write!(f, "307 Temporary Redirect"),
+            RedirectKind::PermanentRedirect => write!(f, "308 Permanent Redirect"),
+            RedirectKind::MetaRefresh => write!(f, "meta refresh"),
+            RedirectKind::Other(status) => write!(f, "{}", status),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct RedirectChain {
+    /// Request numbers of each hop in the chain.
+    pub hops: Vec<usize>,
+    /// URLs of each hop, followed by the final target.
+    pub urls: Vec<String>,
+    /// How the first hop redirected.
+    pub kind: RedirectKind,
+    /// Status of the final response, `None` if it isn't in the capture.
+    pub final_status: Option<u16>,
+}
+
+impl RedirectChain {
+    pub fn ended_in_error(&self) -> bool {
+        self.final_status
+            .is_some_and(|status| status == 0 || status >= 400)
+    }
+}
+
+/// Get the target of an entry that redirects, with how it redirected.
+fn redirect_target(entry: &Entry) -> Option<(String, RedirectKind)> {
+    let response = &entry.response;
+
+    let target = if (300..400).contains(&response.status) && response.status != 304 {
+        let location = Some(response.redirect_url.as_str())
+            .filter(|url| !url.is_empty())
+            .or_else(|| {
+                response
+                    .headers
+                    .iter()
+                    .find(|h| h.name.eq_ignore_ascii_case("location"))
+                    .map(|h| h.value.as_str())
+            })?;
+        (
+            location.to_string(),
+            RedirectKind::from_status(response.status),
+        )
+    } else if response.status == 200 {
+        let content = response.content.as_ref()?;
+        if !content.mime_type.as_deref()?.contains("html") {
+            return None;
+        }
+        let body = decode::decode_body_text(content)?;
+        (meta_refresh_target(&body)?, RedirectKind::MetaRefresh)
+    } else {
+        return None;
+    };
+
+    // targets may be relative to the request URL
+    let resolved = Url::parse(&entry.request.url)
+        .and_then(|base| base.join(&target.0))
+        .map(|url| url.to_string())
+        .unwrap_or(target.0);
+
+    Some((resolved, target.1))
+}
+
+/// Find the target of a `<meta http-equiv="refresh" content="0; url=...">` tag.
+fn meta_refresh_target(body: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let tag_start = lower.find("http-equiv=\"refresh\"")?;
+
+    // the tag containing the attribute
+    let start = lower[..tag_start].rfind('<')?;
+    let end = start + lower[start..].find('>')?;
+    let tag = &lower[start..end];
+
+    let content_start = tag.find("content=")? + "content=".len();
+    let content = tag[content_start..].trim_start_matches(['"', '\'']);
+    let url_start = content.find("url=")? + "url=".len();
+    let url_len = content[url_start..]
+        .find(['"', '\''])
+        .unwrap_or(content.len() - url_start);
+
+    // take the URL from the original body to keep its case
+    let offset = start + content_start + (tag[content_start..].len() - content.len()) + url_start;
+    let url = body[offset..offset + url_len].trim();
+    (!url.is_empty()).then(|| url.to_string())
+}
+
+pub fn redirect_chains(har: &Har) -> Vec<RedirectChain> {
+    let entries = &har.log.entries;
+    let targets: Vec<Option<(String, RedirectKind)>> =
+        entries.iter().map(redirect_target).collect();
+
+    // find the entry a redirect led to, the first later request for the target
+    let next_hop = |i: usize| -> Option<usize> {
+        let (target, _) = targets[i].as_ref()?;
+        (i + 1..entries.len()).find(|&j| entries[j].request.url == *target)
+    };
+
+    let linked: Vec<Option<usize>> = (0..entries.len()).map(next_hop).collect();
+    let mut is_target = vec![false; entries.len()];
+    for &j in linked.iter().flatten() {
+        is_target[j] = true;
+    }
+
+    let mut chains = Vec::new();
+    for start in 0..entries.len() {
+        // chains start at redirects that weren't reached by another redirect
+        let Some((_, kind)) = targets[start] else {
+            continue;
+        };
+        if is_target[start] {
+            continue;
+        }
+
+        let mut hops = vec![start + 1];
+        let mut urls = vec![entries[start].request.url.clone()];
+        let mut current = start;
+        let final_status = loop {
+            match linked[current] {
+                Some(next) if targets[next].is_some() && !hops.contains(&(next + 1)) => {
+                    hops.push(next + 1);
+                    urls.push(entries[next].request.url.clone());
+                    current = next;
+                }
+                Some(next) => {
+                    urls.push(entries[next].request.url.clone());
+                    break Some(entries[next].response.status);
+                }
+                None => {
+                    if let Some((target, _)) = &targets[current] {
+                        urls.push(target.clone());
+                    }
+                    break None;
+                }
+            }
+        };
+
+        chains.push(RedirectChain {
+            hops,
+            urls,
+            kind,
+            final_status,
+        });
+    }
+
+    chains
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyOutcome {
+    /// Status returned by the live target now.
+    Status(u16),
+    /// The request failed, i.e. timed out or didn't resolve.
+    Failed(String),
+    /// The target isn't an HTTP URL.
+    Skipped,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct Verification {
+    /// The chain's final target.
+    pub url: String,
+    /// Status recorded in the capture, `None` if it wasn't captured.
+    pub recorded: Option<u16>,
+    pub outcome: VerifyOutcome,
+}
+
+impl Verification {
+    /// Whether the live target returned the same status as the capture.
+    pub fn unchanged(&self) -> bool {
+        matches!(self.outcome, VerifyOutcome::Status(status) if Some(status) == self.recorded)
+    }
+}
+
+/// Issue a `HEAD` request to the final target of each chain.
+///
+/// Redirects aren't followed, so a target that now redirects elsewhere
+/// reports its own 3xx status. At most `concurrency` requests are in flight.
+pub async fn verify_chains(
+    chains: &[RedirectChain],
+    timeout: Duration,
+    concurrency: usize,
+) -> Result<Vec<Verification>> {
+    let client = Client::builder()
+        .redirect(Policy::none())
+        .timeout(timeout)
+        .build()?;
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+
+    let mut handles = Vec::new();
+    for chain in chains {
+        let url = chain.urls.last().cloned().unwrap_or_default();
+        let recorded = chain.final_status;
+        let client = client.clone();
+        let permits = Arc::clone(&permits);
+
+        handles.push(tokio::spawn(async move {
+            let is_http = Url::parse(&url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+            let outcome = if !is_http {
+                VerifyOutcome::Skipped
+            } else {
+                let _permit = permits.acquire().await;
+                match client.head(&url).send().await {
+                    Ok(response) => VerifyOutcome::Status(response.status().as_u16()),
+                    Err(e) => VerifyOutcome::Failed(e.to_string()),
+                }
+            };
+
+            Verification {
+                url,
+                recorded,
+                outcome,
+            }
+        }));
+    }
+
+    let mut verifications = Vec::new();
+    for handle in handles {
+        verifications.push(handle.await?);
+    }
+
+    Ok(verifications)
+}
@@ -0,0 +1,105 @@
+use crate::Har;
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct RetryGroup {
+    pub method: String,
+    pub url: String,
+    /// Request numbers of each attempt, starting with the first failure.
+    pub request_nums: Vec<usize>,
+    pub statuses: Vec<u16>,
+    /// Milliseconds between the start of each attempt and the next.
+    pub gaps_ms: Vec<f64>,
+}
+
+impl RetryGroup {
+    pub fn attempts(&self) -> usize {
+        self.request_nums.len()
+    }
+
+    pub fn final_status(&self) -> u16 {
+        self.statuses.last().copied().unwrap_or_default()
+    }
+
+    /// Whether a later attempt succeeded.
+    pub fn recovered(&self) -> bool {
+        !is_failure(self.final_status())
+    }
+}
+
+/// One request to a method and URL.
+#[derive(Clone, Copy, Debug)]
+struct Attempt {
+    request_num: usize,
+    /// Start time in microseconds since the epoch.
+    started: i64,
+    status: u16,
+}
+
+fn is_failure(status: u16) -> bool {
+    status == 0 || status >= 400
+}
+
+/// Find requests that failed then were repeated to the same URL with the same
+/// method, up to and including the first attempt that succeeded.
+pub fn find_retries(har: &Har) -> Vec<RetryGroup> {
+    // attempts for each method and URL, in the order they started
+    let mut attempts: HashMap<(&str, &str), Vec<Attempt>> = HashMap::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let Ok(started) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+            log::debug!("Skipping request {} with an invalid startedDateTime", i + 1);
+            continue;
+        };
+
+        attempts
+            .entry((entry.request.method.as_str(), entry.request.url.as_str()))
+            .or_default()
+            .push(Attempt {
+                request_num: i + 1,
+                started: started.timestamp_micros(),
+                status: entry.response.status,
+            });
+    }
+
+    let mut groups = Vec::new();
+
+    for ((method, url), mut attempts) in attempts {
+        attempts.sort_by_key(|a| (a.started, a.request_num));
+
+        let mut i = 0;
+        while i < attempts.len() {
+            if !is_failure(attempts[i].status) {
+                i += 1;
+                continue;
+            }
+
+            // retries continue until one succeeds
+            let end = attempts[i + 1..]
+                .iter()
+                .position(|a| !is_failure(a.status))
+                .map_or(attempts.len(), |offset| i + 1 + offset + 1);
+            let run = &attempts[i..end];
+
+            if run.len() > 1 {
+                groups.push(RetryGroup {
+                    method: method.to_string(),
+                    url: url.to_string(),
+                    request_nums: run.iter().map(|a| a.request_num).collect(),
+                    statuses: run.iter().map(|a| a.status).collect(),
+                    gaps_ms: run
+                        .windows(2)
+                        .map(|pair| (pair[1].started - pair[0].started) as f64 / 1000.0)
+                        .collect(),
+                });
+            }
+            i = end;
+        }
+    }
+
+    groups.sort_by_key(|group| group.request_nums[0]);
+    groups
+}
@@ -1,14 +1,20 @@
-use crate::har::{Har, Request};
+use crate::har::{Entry, Har, PostData, Request};
+use schemars::JsonSchema;
 use serde::Serialize;
+use std::str::FromStr;
 
-#[allow(dead_code)]
-#[derive(Clone, Debug)]
+use super::{decode, list_domains, mime};
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 pub struct SearchResult<'a> {
     pub request_num: usize,
     pub time: String,
     pub url: String,
     pub method: String,
     pub in_fields: Vec<String>,
+    /// The search strings found in the request.
+    pub needles: Vec<String>,
+    #[serde(skip)]
     pub request: &'a Request,
 }
 
@@ -70,71 +76,560 @@ fn check_serialised_field<T: Serialize>(
     }
 }
 
-pub fn search_for<'a>(har: &'a Har, search_str: &'a str) -> Vec<SearchResult<'a>> {
+/// Whether a request has to contain any or all of the search strings to match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NeedleMatch {
+    #[default]
+    Any,
+    All,
+}
+
+pub fn search_for<'a>(har: &'a Har, search_str: &str) -> Vec<SearchResult<'a>> {
+    search_for_needles(har, &[search_str], NeedleMatch::Any)
+}
+
+/// Find the requests containing any or all of the search strings, recording
+/// which were found in each.
+pub fn search_for_needles<'a, S: AsRef<str>>(
+    har: &'a Har,
+    needles: &[S],
+    mode: NeedleMatch,
+) -> Vec<SearchResult<'a>> {
     har.log
         .entries
         .iter()
         .enumerate()
         .filter_map(|(i, entry)| {
-            let mut in_fields = Vec::new();
-            let request = &entry.request;
-            let response = &entry.response;
+            let (in_fields, found) = needle_fields(entry, needles, mode, matching_fields)?;
+            Some(to_result(i, entry, in_fields, found))
+        })
+        .collect()
+}
 
-            // Entry-level fields
-            check_serialised_field(
-                &entry.started_date_time,
-                "startedDateTime",
-                search_str,
-                &mut in_fields,
-                "",
-            );
+/// Find the requests with a JSON body whose value at `path` contains any or
+/// all of the search strings.
+pub fn search_json_path<'a, S: AsRef<str>>(
+    har: &'a Har,
+    path: &JsonPath,
+    needles: &[S],
+    mode: NeedleMatch,
+) -> Vec<SearchResult<'a>> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let (in_fields, found) = needle_fields(entry, needles, mode, |entry, needle| {
+                path.matching_fields(entry, needle)
+            })?;
+            Some(to_result(i, entry, in_fields, found))
+        })
+        .collect()
+}
 
-            // Request fields
-            check_fields!(
-                request,
-                "request",
-                [
-                    ("method", &request.method),
-                    ("url", &request.url),
-                    ("http_version", &request.http_version),
-                    ("headers", &request.headers),
-                    ("cookies", &request.cookies),
-                    ("query_string", &request.query_string),
-                    ("post_data", &request.post_data),
-                ],
-                search_str,
-                &mut in_fields
-            );
+/// Find the requests that aren't matched by the search strings.
+pub fn search_for_missing<'a, S: AsRef<str>>(
+    har: &'a Har,
+    needles: &[S],
+    mode: NeedleMatch,
+) -> Vec<SearchResult<'a>> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| needle_fields(entry, needles, mode, matching_fields).is_none())
+        .map(|(i, entry)| to_result(i, entry, Vec::new(), Vec::new()))
+        .collect()
+}
 
-            // Response fields
-            check_fields!(
-                response,
-                "response",
-                [
-                    ("status", &response.status),
-                    ("status_text", &response.status_text),
-                    ("http_version", &response.http_version),
-                    ("headers", &response.headers),
-                    ("cookies", &response.cookies),
-                    ("content", &response.content),
-                    ("redirect_url", &response.redirect_url),
-                ],
-                search_str,
-                &mut in_fields
-            );
+/// Find the requests without a JSON body whose value at `path` is matched by
+/// the search strings.
+pub fn search_json_path_missing<'a, S: AsRef<str>>(
+    har: &'a Har,
+    path: &JsonPath,
+    needles: &[S],
+    mode: NeedleMatch,
+) -> Vec<SearchResult<'a>> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| {
+            needle_fields(entry, needles, mode, |entry, needle| {
+                path.matching_fields(entry, needle)
+            })
+            .is_none()
+        })
+        .map(|(i, entry)| to_result(i, entry, Vec::new(), Vec::new()))
+        .collect()
+}
 
-            if !in_fields.is_empty() {
-                Some(SearchResult {
-                    request_num: i + 1,
-                    time: entry.started_date_time.clone(),
-                    url: request.url.clone(),
-                    method: request.method.clone(),
-                    in_fields,
-                    request: &entry.request,
-                })
+/// The fields matching any needle and the needles found, if enough were found.
+fn needle_fields<S: AsRef<str>>(
+    entry: &Entry,
+    needles: &[S],
+    mode: NeedleMatch,
+    matching: impl Fn(&Entry, &str) -> Vec<String>,
+) -> Option<(Vec<String>, Vec<String>)> {
+    let mut in_fields: Vec<String> = Vec::new();
+    let mut found = Vec::new();
+
+    for needle in needles {
+        let fields = matching(entry, needle.as_ref());
+        if fields.is_empty() {
+            if mode == NeedleMatch::All {
+                return None;
+            }
+            continue;
+        }
+
+        found.push(needle.as_ref().to_string());
+        for field in fields {
+            if !in_fields.contains(&field) {
+                in_fields.push(field);
+            }
+        }
+    }
+
+    (!found.is_empty()).then_some((in_fields, found))
+}
+
+fn to_result(
+    i: usize,
+    entry: &Entry,
+    in_fields: Vec<String>,
+    needles: Vec<String>,
+) -> SearchResult<'_> {
+    SearchResult {
+        request_num: i + 1,
+        time: entry.started_date_time.clone(),
+        url: entry.request.url.clone(),
+        method: entry.request.method.clone(),
+        in_fields,
+        needles,
+        request: &entry.request,
+    }
+}
+
+fn matching_fields(entry: &Entry, search_str: &str) -> Vec<String> {
+    let mut in_fields = Vec::new();
+    let request = &entry.request;
+    let response = &entry.response;
+
+    // Entry-level fields
+    check_serialised_field(
+        &entry.started_date_time,
+        "startedDateTime",
+        search_str,
+        &mut in_fields,
+        "",
+    );
+
+    // Request fields
+    check_fields!(
+        request,
+        "request",
+        [
+            ("method", &request.method),
+            ("url", &request.url),
+            ("http_version", &request.http_version),
+            ("headers", &request.headers),
+            ("cookies", &request.cookies),
+            ("query_string", &request.query_string),
+            ("post_data", &request.post_data),
+        ],
+        search_str,
+        &mut in_fields
+    );
+
+    // Response fields
+    check_fields!(
+        response,
+        "response",
+        [
+            ("status", &response.status),
+            ("status_text", &response.status_text),
+            ("http_version", &response.http_version),
+            ("headers", &response.headers),
+            ("cookies", &response.cookies),
+            ("content", &response.content),
+            ("redirect_url", &response.redirect_url),
+        ],
+        search_str,
+        &mut in_fields
+    );
+
+    // form fields may be percent encoded or escaped in JSON, so check each one decoded
+    if let Some(post_data) = &request.post_data {
+        if body_params(post_data)
+            .iter()
+            .any(|(name, value)| name.contains(search_str) || value.contains(search_str))
+        {
+            in_fields.push("request_body_param".to_string());
+        }
+    }
+
+    // encoded bodies won't match as-is, so check the decoded text too
+    if let Some(content) = response
+        .content
+        .as_ref()
+        .filter(|c| c.encoding.as_deref() == Some("base64"))
+    {
+        if decode::decode_body_text(content).is_some_and(|text| text.contains(search_str)) {
+            in_fields.push("response_content_decoded".to_string());
+        }
+    }
+
+    in_fields
+}
+
+/// Name/value pairs of a request body, from the params array and from
+/// url-encoded or JSON text.
+fn body_params(post_data: &PostData) -> Vec<(String, String)> {
+    let mut params: Vec<(String, String)> = post_data
+        .params
+        .iter()
+        .flatten()
+        .map(|p| (p.name.clone(), p.value.clone().unwrap_or_default()))
+        .collect();
+
+    let mime_type = mime::normalise(&post_data.mime_type);
+    if mime_type == "application/x-www-form-urlencoded" {
+        params.extend(
+            url::form_urlencoded::parse(post_data.text.as_bytes())
+                .map(|(name, value)| (name.into_owned(), value.into_owned())),
+        );
+    } else if mime_type == "application/json" || mime_type.ends_with("+json") {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(&post_data.text) {
+            json_params(&value, "", &mut params);
+        }
+    }
+
+    params
+}
+
+/// Flatten a JSON value into key/value pairs, keys are the path to each value.
+fn json_params(value: &serde_json::Value, path: &str, params: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                json_params(value, &path, params);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                json_params(value, &format!("{}[{}]", path, i), params);
+            }
+        }
+        serde_json::Value::String(s) => params.push((path.to_string(), s.clone())),
+        value => params.push((path.to_string(), value.to_string())),
+    }
+}
+
+fn is_json(mime_type: &str) -> bool {
+    let mime_type = mime::normalise(mime_type);
+    mime_type == "application/json" || mime_type.ends_with("+json")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+    /// Every member of an object or item of an array.
+    Wildcard,
+}
+
+/// A path into a JSON document, like `$.data.items[0].token`.
+///
+/// Keys follow `.`, array indexes are in brackets and `*` or `[*]` matches
+/// every member. The leading `$` is optional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath(Vec<PathSegment>);
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut rest, mut bare_key) = match s.strip_prefix('$') {
+            Some(rest) => (rest, false),
+            None => (s, true),
+        };
+        let mut segments = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(index) = rest.strip_prefix('[') {
+                let Some((index, after)) = index.split_once(']') else {
+                    return Err(format!("unterminated '[' in path '{}'", s));
+                };
+                segments.push(match index {
+                    "*" => PathSegment::Wildcard,
+                    index => {
+                        PathSegment::Index(index.parse().map_err(|_| {
+                            format!("invalid array index '{}' in path '{}'", index, s)
+                        })?)
+                    }
+                });
+                rest = after;
             } else {
-                None
+                // a path without the `$` may start with a bare key
+                let key = match rest.strip_prefix('.') {
+                    Some(key) => key,
+                    None if bare_key => rest,
+                    None => return Err(format!("expected '.' or '[' in path '{}'", s)),
+                };
+                let end = key.find(['.', '[']).unwrap_or(key.len());
+                segments.push(match &key[..end] {
+                    "" => return Err(format!("empty key in path '{}'", s)),
+                    "*" => PathSegment::Wildcard,
+                    name => PathSegment::Key(name.to_string()),
+                });
+                rest = &key[end..];
             }
+            bare_key = false;
+        }
+
+        Ok(JsonPath(segments))
+    }
+}
+
+impl JsonPath {
+    /// The values at this path, more than one if it has wildcards.
+    pub fn select<'a>(&self, value: &'a serde_json::Value) -> Vec<&'a serde_json::Value> {
+        let mut selected = vec![value];
+
+        for segment in &self.0 {
+            selected = selected
+                .into_iter()
+                .flat_map(|value| -> Vec<&serde_json::Value> {
+                    match (segment, value) {
+                        (PathSegment::Key(key), serde_json::Value::Object(map)) => {
+                            map.get(key).into_iter().collect()
+                        }
+                        (PathSegment::Index(i), serde_json::Value::Array(items)) => {
+                            items.get(*i).into_iter().collect()
+                        }
+                        (PathSegment::Wildcard, serde_json::Value::Object(map)) => {
+                            map.values().collect()
+                        }
+                        (PathSegment::Wildcard, serde_json::Value::Array(items)) => {
+                            items.iter().collect()
+                        }
+                        _ => Vec::new(),
+                    }
+                })
+                .collect();
+        }
+
+        selected
+    }
+
+    /// Whether a value at this path contains the search string. Strings are
+    /// compared as they are, anything else as serialised JSON.
+    fn matches(&self, value: &serde_json::Value, search_str: &str) -> bool {
+        self.select(value).into_iter().any(|value| match value {
+            serde_json::Value::String(s) => s.contains(search_str),
+            value => value.to_string().contains(search_str),
         })
-        .collect()
+    }
+
+    fn matching_fields(&self, entry: &Entry, search_str: &str) -> Vec<String> {
+        let mut in_fields = Vec::new();
+
+        let request_body = entry
+            .request
+            .post_data
+            .as_ref()
+            .filter(|post_data| is_json(&post_data.mime_type))
+            .map(|post_data| post_data.text.clone());
+        let response_body = entry
+            .response
+            .content
+            .as_ref()
+            .filter(|content| content.mime_type.as_deref().is_some_and(is_json))
+            .and_then(decode::decode_body_text);
+
+        for (field, body) in [
+            ("request_body_json", request_body),
+            ("response_content_json", response_body),
+        ] {
+            let Some(value) = body.and_then(|body| serde_json::from_str(&body).ok()) else {
+                continue;
+            };
+            if self.matches(&value, search_str) {
+                in_fields.push(field.to_string());
+            }
+        }
+
+        in_fields
+    }
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct FuzzyMatch {
+    pub needle: String,
+    pub host: String,
+    pub distance: usize,
+}
+
+/// Find hosts in the capture within an edit distance of the search string.
+///
+/// Each host is also compared with its parent domains, so `paypa1.com` is
+/// found as a near miss from `www.paypa1.com`. Exact matches are excluded as
+/// the regular search already reports them.
+pub fn fuzzy_hosts(har: &Har, search_str: &str, max_distance: usize) -> Vec<FuzzyMatch> {
+    let needle = search_str.to_lowercase();
+
+    let mut matches: Vec<FuzzyMatch> = list_domains::list_domains(har)
+        .into_iter()
+        .filter_map(|host| {
+            let host_lower = host.to_lowercase();
+            let labels: Vec<&str> = host_lower.split('.').collect();
+
+            let distance = (0..labels.len())
+                .map(|i| levenshtein(&needle, &labels[i..].join(".")))
+                .min()?;
+
+            (distance > 0 && distance <= max_distance).then_some(FuzzyMatch {
+                needle: search_str.to_string(),
+                host,
+                distance,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        a.distance
+            .cmp(&b.distance)
+            .then_with(|| a.host.cmp(&b.host))
+    });
+    matches
+}
+
+/// Number of single character insertions, deletions or substitutions between strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+
+    // distances from the previous row, starting with an empty prefix of a
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TemplateField {
+    RequestNum,
+    Time,
+    Url,
+    Method,
+    InFields,
+    Needles,
+}
+
+#[derive(Debug, Clone)]
+enum TemplatePart {
+    Literal(String),
+    Field(TemplateField),
+}
+
+/// A template for printing search results, like `{request_num} {method} {url}`.
+///
+/// Use `{{` and `}}` for literal braces.
+#[derive(Debug, Clone)]
+pub struct ResultTemplate(Vec<TemplatePart>);
+
+impl FromStr for ResultTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unterminated placeholder '{{{}'", name)),
+                        }
+                    }
+
+                    let field = match name.as_str() {
+                        "request_num" => TemplateField::RequestNum,
+                        "time" => TemplateField::Time,
+                        "url" => TemplateField::Url,
+                        "method" => TemplateField::Method,
+                        "in_fields" => TemplateField::InFields,
+                        "needles" => TemplateField::Needles,
+                        _ => {
+                            return Err(format!(
+                                "unknown placeholder '{{{}}}', expected one of: {{request_num}}, {{time}}, {{url}}, {{method}}, {{in_fields}}, {{needles}}",
+                                name
+                            ))
+                        }
+                    };
+
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Field(field));
+                }
+                '}' => {
+                    return Err(
+                        "unmatched '}' in template, use '}}' for a literal brace".to_string()
+                    )
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+
+        Ok(ResultTemplate(parts))
+    }
+}
+
+impl ResultTemplate {
+    pub fn render(&self, result: &SearchResult) -> String {
+        self.0
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::Field(TemplateField::RequestNum) => result.request_num.to_string(),
+                TemplatePart::Field(TemplateField::Time) => result.time.clone(),
+                TemplatePart::Field(TemplateField::Url) => result.url.clone(),
+                TemplatePart::Field(TemplateField::Method) => result.method.clone(),
+                TemplatePart::Field(TemplateField::InFields) => result.in_fields.join(","),
+                TemplatePart::Field(TemplateField::Needles) => result.needles.join(","),
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,232 @@
+use crate::Har;
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Slack allowed before requests count as overlapping, as tools round the
+/// start time and duration separately.
+const TOLERANCE_MS: f64 = 1.0;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct SerialRun {
+    pub connection: String,
+    /// Request numbers in the order they ran.
+    pub request_nums: Vec<usize>,
+    /// Milliseconds from the first request starting to the last one finishing.
+    pub span_ms: f64,
+    /// Milliseconds the connection sat idle between requests.
+    pub idle_ms: f64,
+    /// Milliseconds saved if every request had started with the first, so only
+    /// the longest is waited for.
+    pub savings_ms: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct Serialization {
+    /// Runs of requests that waited for each other, most savings first.
+    pub runs: Vec<SerialRun>,
+    /// Requests that started while an earlier one on their connection was in flight.
+    pub overlapping: usize,
+}
+
+/// When a request was in flight, in milliseconds since the epoch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Span {
+    request_num: usize,
+    start: f64,
+    end: f64,
+}
+
+impl Span {
+    fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Find requests that ran one after another on the same connection, and how
+/// much sooner they would have finished if sent in parallel. A connection idle
+/// for more than `max_gap_ms` ends a run, as the next request likely wasn't
+/// ready to send, and runs saving less than `min_savings_ms` are left out.
+pub fn find_serialization(har: &Har, max_gap_ms: f64, min_savings_ms: f64) -> Serialization {
+    let mut connections: HashMap<&str, Vec<Span>> = HashMap::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let Some(connection) = entry.connection.as_deref().filter(|c| !c.is_empty()) else {
+            continue;
+        };
+        let Ok(started) = DateTime::parse_from_rfc3339(&entry.started_date_time) else {
+            log::debug!("Skipping request {} with an invalid startedDateTime", i + 1);
+            continue;
+        };
+
+        let start = started.timestamp_micros() as f64 / 1000.0;
+        connections.entry(connection).or_default().push(Span {
+            request_num: i + 1,
+            start,
+            end: start + entry.time.max(0.0),
+        });
+    }
+
+    let mut serialization = Serialization::default();
+
+    for (connection, mut spans) in connections {
+        spans.sort_by(|a, b| {
+            a.start
+                .total_cmp(&b.start)
+                .then(a.request_num.cmp(&b.request_num))
+        });
+
+        let (runs, overlapping) = split_runs(&spans, max_gap_ms);
+        serialization.overlapping += overlapping;
+        serialization.runs.extend(
+            runs.into_iter()
+                .map(|run| to_run(connection, run))
+                .filter(|run| run.savings_ms >= min_savings_ms),
+        );
+    }
+
+    serialization
+        .runs
+        .sort_by(|a, b| b.savings_ms.total_cmp(&a.savings_ms));
+    serialization
+}
+
+/// Split spans sorted by start time into runs where each request started after
+/// the previous ones finished, within `max_gap_ms`, keeping runs of more than
+/// one request. Also returns how many requests overlapped an earlier one, these
+/// start a new run.
+fn split_runs(spans: &[Span], max_gap_ms: f64) -> (Vec<&[Span]>, usize) {
+    let mut runs = Vec::new();
+    let mut overlapping = 0;
+    let mut run_start = 0;
+    // the latest end so far, an overlapping request may finish first
+    let mut busy_until = f64::NEG_INFINITY;
+
+    for (i, span) in spans.iter().enumerate() {
+        let overlaps = span.start + TOLERANCE_MS < busy_until;
+        if overlaps {
+            overlapping += 1;
+        }
+        if overlaps || span.start - busy_until > max_gap_ms {
+            if i - run_start > 1 {
+                runs.push(&spans[run_start..i]);
+            }
+            run_start = i;
+        }
+        busy_until = busy_until.max(span.end);
+    }
+
+    if spans.len() - run_start > 1 {
+        runs.push(&spans[run_start..]);
+    }
+
+    (runs, overlapping)
+}
+
+fn to_run(connection: &str, run: &[Span]) -> SerialRun {
+    let span_ms = run[run.len() - 1].end - run[0].start;
+    let longest = run.iter().map(Span::duration).fold(0.0, f64::max);
+
+    SerialRun {
+        connection: connection.to_string(),
+        request_nums: run.iter().map(|span| span.request_num).collect(),
+        span_ms,
+        idle_ms: run
+            .windows(2)
+            .map(|pair| (pair[1].start - pair[0].end).max(0.0))
+            .sum(),
+        savings_ms: span_ms - longest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Spans from `(start, duration)` pairs, numbered from 1.
+    fn spans(timings: &[(f64, f64)]) -> Vec<Span> {
+        timings
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, duration))| Span {
+                request_num: i + 1,
+                start,
+                end: start + duration,
+            })
+            .collect()
+    }
+
+    const MAX_GAP_MS: f64 = 1000.0;
+
+    fn request_nums(runs: &[&[Span]]) -> Vec<Vec<usize>> {
+        runs.iter()
+            .map(|run| run.iter().map(|span| span.request_num).collect())
+            .collect()
+    }
+
+    #[test]
+    fn sequential_requests_form_one_run() {
+        let spans = spans(&[(0.0, 100.0), (150.0, 50.0), (250.0, 200.0)]);
+        let (runs, overlapping) = split_runs(&spans, MAX_GAP_MS);
+
+        assert_eq!(request_nums(&runs), [[1, 2, 3]]);
+        assert_eq!(overlapping, 0);
+
+        let run = to_run("1", runs[0]);
+        assert_eq!(run.span_ms, 450.0);
+        assert_eq!(run.idle_ms, 100.0);
+        assert_eq!(run.savings_ms, 250.0);
+    }
+
+    #[test]
+    fn overlapping_requests_are_not_serial() {
+        let spans = spans(&[(0.0, 100.0), (10.0, 100.0), (20.0, 100.0)]);
+        let (runs, overlapping) = split_runs(&spans, MAX_GAP_MS);
+
+        assert!(runs.is_empty());
+        assert_eq!(overlapping, 2);
+    }
+
+    #[test]
+    fn overlap_splits_runs() {
+        // 3 starts while 2 is in flight, then 4 waits for both
+        let spans = spans(&[(0.0, 10.0), (20.0, 100.0), (50.0, 20.0), (130.0, 10.0)]);
+        let (runs, overlapping) = split_runs(&spans, MAX_GAP_MS);
+
+        assert_eq!(request_nums(&runs), [vec![1, 2], vec![3, 4]]);
+        assert_eq!(overlapping, 1);
+    }
+
+    #[test]
+    fn long_gaps_split_runs() {
+        let spans = spans(&[
+            (0.0, 100.0),
+            (200.0, 100.0),
+            (5000.0, 100.0),
+            (5100.0, 50.0),
+        ]);
+        let (runs, overlapping) = split_runs(&spans, MAX_GAP_MS);
+
+        assert_eq!(request_nums(&runs), [[1, 2], [3, 4]]);
+        assert_eq!(overlapping, 0);
+    }
+
+    #[test]
+    fn rounding_within_tolerance_is_serial() {
+        let spans = spans(&[(0.0, 100.4), (100.0, 50.0)]);
+        let (runs, overlapping) = split_runs(&spans, MAX_GAP_MS);
+
+        assert_eq!(request_nums(&runs), [[1, 2]]);
+        assert_eq!(overlapping, 0);
+    }
+
+    #[test]
+    fn single_requests_are_not_runs() {
+        assert_eq!(
+            split_runs(&spans(&[(0.0, 100.0)]), MAX_GAP_MS),
+            (Vec::new(), 0)
+        );
+        assert_eq!(split_runs(&[], MAX_GAP_MS), (Vec::new(), 0));
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{har::Entry, Har};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::{bytes, stats::Stats};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RatioKind {
+    /// A small request got a much larger response.
+    Amplified,
+    /// A large request got a much smaller response.
+    Shrunk,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct SizeRatio {
+    pub request_num: usize,
+    pub method: String,
+    pub url: String,
+    /// Bytes of the request headers and body.
+    pub request_bytes: u64,
+    /// Bytes of the response headers and body.
+    pub response_bytes: u64,
+    /// Response bytes per request byte.
+    pub ratio: f64,
+    pub kind: RatioKind,
+}
+
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct SizeRatios {
+    /// Number of requests with both sizes recorded.
+    pub measured: usize,
+    pub median_ratio: Option<f64>,
+    /// Requests past the threshold, most lopsided first.
+    pub flagged: Vec<SizeRatio>,
+}
+
+/// Bytes sent and received by an entry, `None` if the capture doesn't record
+/// either body size.
+fn sizes(entry: &Entry) -> Option<(u64, u64)> {
+    let request_body = u64::try_from(entry.request.body_size).ok()?;
+    let request = bytes::request_headers_size(&entry.request) + request_body;
+    let response = bytes::transferred_bytes(entry)?;
+
+    (request > 0).then_some((request, response))
+}
+
+/// Compare the response size of each request with its own size, flagging those
+/// where one is at least `threshold` times the other and the larger is at least
+/// `min_bytes`. Entries without both sizes are left out.
+pub fn size_ratios(har: &Har, threshold: f64, min_bytes: u64) -> SizeRatios {
+    let mut ratios = Vec::new();
+    let mut flagged = Vec::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let Some((request_bytes, response_bytes)) = sizes(entry) else {
+            log::debug!("Skipping request {} without recorded sizes", i + 1);
+            continue;
+        };
+
+        let ratio = response_bytes as f64 / request_bytes as f64;
+        ratios.push(ratio);
+
+        if request_bytes.max(response_bytes) < min_bytes {
+            continue;
+        }
+        let kind = if ratio >= threshold {
+            RatioKind::Amplified
+        } else if ratio <= 1.0 / threshold {
+            RatioKind::Shrunk
+        } else {
+            continue;
+        };
+
+        flagged.push(SizeRatio {
+            request_num: i + 1,
+            method: entry.request.method.clone(),
+            url: entry.request.url.clone(),
+            request_bytes,
+            response_bytes,
+            ratio,
+            kind,
+        });
+    }
+
+    // the furthest from 1 either way
+    flagged.sort_by(|a, b| b.ratio.ln().abs().total_cmp(&a.ratio.ln().abs()));
+
+    SizeRatios {
+        measured: ratios.len(),
+        median_ratio: Stats::from_values(&ratios).map(|stats| stats.median),
+        flagged,
+    }
+}
@@ -0,0 +1,56 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::{decode, mime};
+
+/// Markers commonly found in error pages.
+pub const DEFAULT_MARKERS: [&str; 8] = [
+    "404 Not Found",
+    "Page Not Found",
+    "500 Internal Server Error",
+    "Internal Server Error",
+    "503 Service Unavailable",
+    "Access Denied",
+    "403 Forbidden",
+    "Bad Gateway",
+];
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct SoftError {
+    pub request_num: usize,
+    pub url: String,
+    pub marker: String,
+}
+
+/// Find 200 responses whose body looks like an error page.
+pub fn find_soft_errors(har: &Har, markers: &[String]) -> Vec<SoftError> {
+    let markers_lower: Vec<String> = markers.iter().map(|m| m.to_lowercase()).collect();
+
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let response = &entry.response;
+            if response.status != 200 {
+                return None;
+            }
+
+            let content = response.content.as_ref()?;
+            let mime_type = mime::normalise(content.mime_type.as_deref()?);
+            if !mime::is_textual(&mime_type) {
+                return None;
+            }
+
+            let body = decode::decode_body_text(content)?.to_lowercase();
+            let index = markers_lower.iter().position(|m| body.contains(m))?;
+
+            Some(SoftError {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                marker: markers[index].clone(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,38 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Summary statistics over a set of durations or sizes.
+#[derive(Clone, Debug, Default, Serialize, JsonSchema)]
+pub struct Stats {
+    pub count: usize,
+    pub total: f64,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl Stats {
+    /// Compute statistics over the values, `None` if there aren't any.
+    pub fn from_values(values: &[f64]) -> Option<Stats> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let count = sorted.len();
+        let total: f64 = sorted.iter().sum();
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2.0
+        } else {
+            sorted[count / 2]
+        };
+
+        Some(Stats {
+            count,
+            total,
+            mean: total / count as f64,
+            median,
+        })
+    }
+}
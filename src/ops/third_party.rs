@@ -0,0 +1,50 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::{
+    bytes,
+    domain_lists::{Party, PartyClassifier},
+};
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct PartyDomain {
+    pub domain: String,
+    pub party: Party,
+    pub requests: usize,
+    /// Response bytes transferred, for the requests that record a size.
+    pub bytes: u64,
+}
+
+/// Count the requests to each registrable domain and classify it, first party
+/// domains first then trackers, each ordered by number of requests.
+pub fn third_parties(har: &Har, classifier: &PartyClassifier) -> Vec<PartyDomain> {
+    let mut domains: HashMap<String, PartyDomain> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let Some((domain, party)) = classifier.classify_url(&entry.request.url) else {
+            continue;
+        };
+
+        let result = domains
+            .entry(domain.clone())
+            .or_insert_with(|| PartyDomain {
+                domain,
+                party,
+                requests: 0,
+                bytes: 0,
+            });
+        result.requests += 1;
+        result.bytes += bytes::transferred_bytes(entry).unwrap_or(0);
+    }
+
+    let mut results: Vec<PartyDomain> = domains.into_values().collect();
+    results.sort_by(|a, b| {
+        a.party
+            .cmp(&b.party)
+            .then(b.requests.cmp(&a.requests))
+            .then_with(|| a.domain.cmp(&b.domain))
+    });
+    results
+}
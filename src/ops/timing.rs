@@ -0,0 +1,94 @@
+use crate::har::Timing;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+
+/// A phase of the `timings` object of an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Blocked,
+    Dns,
+    Connect,
+    Ssl,
+    Send,
+    Wait,
+    Receive,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 7] = [
+        Phase::Blocked,
+        Phase::Dns,
+        Phase::Connect,
+        Phase::Ssl,
+        Phase::Send,
+        Phase::Wait,
+        Phase::Receive,
+    ];
+
+    /// Phases that add up to the entry's `time`, ssl is already part of connect.
+    pub const SUMMED: [Phase; 6] = [
+        Phase::Blocked,
+        Phase::Dns,
+        Phase::Connect,
+        Phase::Send,
+        Phase::Wait,
+        Phase::Receive,
+    ];
+
+    /// The name of the phase as used by the HAR `timings` keys.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::Blocked => "blocked",
+            Phase::Dns => "dns",
+            Phase::Connect => "connect",
+            Phase::Ssl => "ssl",
+            Phase::Send => "send",
+            Phase::Wait => "wait",
+            Phase::Receive => "receive",
+        }
+    }
+
+    /// Get the time spent in the phase, `None` if it's missing or `-1`.
+    pub fn value(&self, timing: &Timing) -> Option<f64> {
+        self.recorded(timing).filter(|&v| v >= 0.0)
+    }
+
+    /// Get the value recorded for the phase as-is, including `-1`.
+    pub fn recorded(&self, timing: &Timing) -> Option<f64> {
+        match self {
+            Phase::Blocked => timing.blocked,
+            Phase::Dns => timing.dns,
+            Phase::Connect => timing.connect,
+            Phase::Ssl => timing.ssl,
+            Phase::Send => Some(timing.send),
+            Phase::Wait => Some(timing.wait),
+            Phase::Receive => Some(timing.receive),
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Phase::ALL
+            .into_iter()
+            .find(|phase| phase.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Phase::ALL.iter().map(|p| p.name()).collect();
+                format!(
+                    "unknown phase '{}', expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,55 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::timing::Phase;
+
+// phases up to and including waiting for the first byte, ssl is already part
+// of connect as in Phase::SUMMED
+const TTFB_PHASES: [Phase; 5] = [
+    Phase::Blocked,
+    Phase::Dns,
+    Phase::Connect,
+    Phase::Send,
+    Phase::Wait,
+];
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct TtfbResult {
+    pub request_num: usize,
+    pub url: String,
+    pub method: String,
+    pub ttfb: f64,
+}
+
+/// Compute the time to first byte of each request, slowest first.
+///
+/// TLS setup is counted once, as part of `connect`, rather than adding `ssl`
+/// on top as a literal reading of the phases would.
+pub fn ttfb(har: &Har) -> Vec<TtfbResult> {
+    let mut results: Vec<TtfbResult> = har
+        .log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let timing = entry.timings.as_ref()?;
+
+            // missing phases count as zero
+            let ttfb = TTFB_PHASES
+                .iter()
+                .filter_map(|phase| phase.value(timing))
+                .sum();
+
+            Some(TtfbResult {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                method: entry.request.method.clone(),
+                ttfb,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.ttfb.total_cmp(&a.ttfb));
+    results
+}
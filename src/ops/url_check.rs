@@ -0,0 +1,118 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+use url::Url;
+
+/// Schemes whose URLs never have a host.
+const HOSTLESS_SCHEMES: [&str; 4] = ["data", "blob", "about", "javascript"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UrlProblem {
+    /// The `url` crate can't parse it, so commands skip the request.
+    Unparseable {
+        error: String,
+    },
+    /// It parses, but has no host to attribute the request to.
+    MissingHost,
+    /// Whitespace the parser strips or percent encodes, so the request was
+    /// likely sent to a different URL than recorded.
+    Whitespace,
+    ControlCharacters,
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct MalformedUrl {
+    pub request_num: usize,
+    pub url: String,
+    pub problems: Vec<UrlProblem>,
+}
+
+/// Find request URLs that don't parse or look malformed, from a corrupt
+/// capture or an injection attempt. Parsing uses the same `url` crate the
+/// host extracting commands do, so an unparseable URL here is one they skip.
+pub fn check_urls(har: &Har) -> Vec<MalformedUrl> {
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let problems = url_problems(&entry.request.url);
+            (!problems.is_empty()).then(|| MalformedUrl {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                problems,
+            })
+        })
+        .collect()
+}
+
+fn url_problems(raw: &str) -> Vec<UrlProblem> {
+    let mut problems = Vec::new();
+
+    match Url::parse(raw) {
+        Ok(url) => {
+            if url.host_str().is_none_or(str::is_empty) && !HOSTLESS_SCHEMES.contains(&url.scheme())
+            {
+                problems.push(UrlProblem::MissingHost);
+            }
+        }
+        Err(e) => problems.push(UrlProblem::Unparseable {
+            error: e.to_string(),
+        }),
+    }
+
+    // the control characters include tabs and newlines, reported as whitespace
+    if raw.chars().any(char::is_whitespace) {
+        problems.push(UrlProblem::Whitespace);
+    }
+    if raw.chars().any(|c| c.is_control() && !c.is_whitespace()) {
+        problems.push(UrlProblem::ControlCharacters);
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn well_formed_urls_have_no_problems() {
+        for url in [
+            "https://example.com/",
+            "http://127.0.0.1:8080/path?q=a%20b",
+            "wss://example.com/socket",
+            "data:image/png;base64,iVBORw0KGgo=",
+            "blob:https://example.com/1b2c",
+            "about:blank",
+        ] {
+            assert!(url_problems(url).is_empty(), "{}", url);
+        }
+    }
+
+    #[test]
+    fn malformed_urls_are_flagged() {
+        assert!(matches!(
+            url_problems("not a url")[..],
+            [UrlProblem::Unparseable { .. }, UrlProblem::Whitespace]
+        ));
+        assert!(matches!(
+            url_problems("https://")[..],
+            [UrlProblem::Unparseable { .. }]
+        ));
+        assert_eq!(url_problems("mailto:someone"), [UrlProblem::MissingHost]);
+        assert_eq!(
+            url_problems("https://example.com/a b"),
+            [UrlProblem::Whitespace]
+        );
+        assert_eq!(
+            url_problems("https://example.com/\ninjected"),
+            [UrlProblem::Whitespace]
+        );
+        assert_eq!(
+            url_problems("https://example.com/\u{0}"),
+            [UrlProblem::ControlCharacters]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use crate::Har;
+use std::collections::HashMap;
+use url::Url;
+
+/// Get the distinct request URLs with their counts, in order of first appearance.
+pub fn unique_urls(har: &Har, strip_query: bool) -> Vec<(String, usize)> {
+    let mut urls: Vec<(String, usize)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for entry in &har.log.entries {
+        let url = if strip_query {
+            strip_url_query(&entry.request.url)
+        } else {
+            entry.request.url.clone()
+        };
+
+        match index.get(&url) {
+            Some(&i) => urls[i].1 += 1,
+            None => {
+                index.insert(url.clone(), urls.len());
+                urls.push((url, 1));
+            }
+        }
+    }
+
+    urls
+}
+
+/// Remove the query string and fragment from a URL.
+pub fn strip_url_query(url_str: &str) -> String {
+    match Url::parse(url_str) {
+        Ok(mut url) => {
+            url.set_query(None);
+            url.set_fragment(None);
+            url.to_string()
+        }
+        Err(_) => url_str
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(url_str)
+            .to_string(),
+    }
+}
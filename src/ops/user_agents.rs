@@ -0,0 +1,23 @@
+use crate::Har;
+use std::collections::BTreeMap;
+
+/// Count the requests sent with each distinct `User-Agent` header.
+///
+/// Requests without the header are counted under an empty string.
+pub fn user_agents(har: &Har) -> BTreeMap<String, usize> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+
+    for entry in &har.log.entries {
+        let user_agent = entry
+            .request
+            .headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case("user-agent"))
+            .map(|h| h.value.clone())
+            .unwrap_or_default();
+
+        *counts.entry(user_agent).or_insert(0) += 1;
+    }
+
+    counts
+}
@@ -0,0 +1,174 @@
+use crate::{har::Entry, Har};
+use chrono::DateTime;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashSet;
+
+use super::timing::Phase;
+
+/// Slack allowed when comparing the phases with the total, as tools round each
+/// value separately.
+const TOLERANCE_MS: f64 = 1.0;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimingProblem {
+    /// The phases add up to more than the entry's total time.
+    SumExceedsTime { sum: f64, time: f64 },
+    /// A phase is negative without being the `-1` sentinel for not applicable.
+    Negative { phase: Phase, value: f64 },
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct TimingIssue {
+    pub request_num: usize,
+    pub url: String,
+    pub problem: TimingProblem,
+}
+
+/// Find entries with timings that can't be right, i.e. from a buggy capture tool.
+pub fn timing_check(har: &Har) -> Vec<TimingIssue> {
+    let mut issues = Vec::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        issues.extend(
+            timing_problems(entry)
+                .into_iter()
+                .map(|problem| TimingIssue {
+                    request_num: i + 1,
+                    url: entry.request.url.clone(),
+                    problem,
+                }),
+        );
+    }
+
+    issues
+}
+
+fn timing_problems(entry: &Entry) -> Vec<TimingProblem> {
+    let Some(timing) = &entry.timings else {
+        return Vec::new();
+    };
+    let mut problems = Vec::new();
+
+    for phase in Phase::ALL {
+        if let Some(value) = phase.recorded(timing).filter(|&v| v < 0.0 && v != -1.0) {
+            problems.push(TimingProblem::Negative { phase, value });
+        }
+    }
+
+    let sum: f64 = Phase::SUMMED
+        .iter()
+        .filter_map(|phase| phase.value(timing))
+        .sum();
+    if sum > entry.time + TOLERANCE_MS {
+        problems.push(TimingProblem::SumExceedsTime {
+            sum,
+            time: entry.time,
+        });
+    }
+
+    problems
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EntryProblem {
+    /// A field the spec requires that parsing lets through, i.e. `timings`.
+    Missing {
+        field: String,
+    },
+    /// `startedDateTime` isn't an ISO 8601 date, so the entry can't be placed in time.
+    InvalidDate {
+        value: String,
+    },
+    /// `pageref` names a page that isn't in the capture.
+    UnknownPage {
+        pageref: String,
+    },
+    Timing {
+        problem: TimingProblem,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct MalformedEntry {
+    pub request_num: usize,
+    pub url: String,
+    pub problems: Vec<EntryProblem>,
+}
+
+/// Find entries that don't follow the spec, where commands would otherwise
+/// skip them or the fields they're missing. Covers the required fields parsed
+/// leniently, dates, page references and the checks of [`timing_check`].
+pub fn strict_check(har: &Har) -> Vec<MalformedEntry> {
+    let page_ids: HashSet<&str> = har
+        .log
+        .pages
+        .iter()
+        .flatten()
+        .map(|page| page.id.as_str())
+        .collect();
+
+    har.log
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| {
+            let problems = entry_problems(entry, &page_ids);
+            (!problems.is_empty()).then(|| MalformedEntry {
+                request_num: i + 1,
+                url: entry.request.url.clone(),
+                problems,
+            })
+        })
+        .collect()
+}
+
+fn entry_problems(entry: &Entry, page_ids: &HashSet<&str>) -> Vec<EntryProblem> {
+    let mut problems = Vec::new();
+    let mut missing = |present: bool, field: &str| {
+        if !present {
+            problems.push(EntryProblem::Missing {
+                field: field.to_string(),
+            });
+        }
+    };
+
+    let request = &entry.request;
+    let response = &entry.response;
+    missing(entry.timings.is_some(), "timings");
+    missing(request.headers_size.is_some(), "request.headersSize");
+    missing(response.headers_size.is_some(), "response.headersSize");
+    match &response.content {
+        Some(content) => {
+            missing(content.size.is_some(), "response.content.size");
+            missing(content.mime_type.is_some(), "response.content.mimeType");
+        }
+        None => missing(false, "response.content"),
+    }
+
+    if DateTime::parse_from_rfc3339(&entry.started_date_time).is_err() {
+        problems.push(EntryProblem::InvalidDate {
+            value: entry.started_date_time.clone(),
+        });
+    }
+
+    if let Some(pageref) = entry
+        .pageref
+        .as_deref()
+        .filter(|pageref| !page_ids.contains(pageref))
+    {
+        problems.push(EntryProblem::UnknownPage {
+            pageref: pageref.to_string(),
+        });
+    }
+
+    problems.extend(
+        timing_problems(entry)
+            .into_iter()
+            .map(|problem| EntryProblem::Timing { problem }),
+    );
+
+    problems
+}
@@ -0,0 +1,38 @@
+use crate::Har;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::query_params;
+
+#[derive(Clone, Debug, Serialize, JsonSchema)]
+pub struct ParamMatch {
+    pub request_num: usize,
+    pub url: String,
+    pub value: String,
+}
+
+pub fn where_param(har: &Har, name: &str, value: Option<&str>) -> Vec<ParamMatch> {
+    let mut matches = Vec::new();
+
+    for (i, entry) in har.log.entries.iter().enumerate() {
+        let url = &entry.request.url;
+        let Some(pairs) = query_params::parse_query(url) else {
+            continue;
+        };
+
+        // report every occurrence, parameters can be repeated
+        for (param_name, param_value) in pairs {
+            if param_name != name || value.is_some_and(|v| v != param_value) {
+                continue;
+            }
+
+            matches.push(ParamMatch {
+                request_num: i + 1,
+                url: url.clone(),
+                value: param_value,
+            });
+        }
+    }
+
+    matches
+}
@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+use terminal_size::{terminal_size, Height};
+
+/// Write text to `out`, piping it through `$PAGER` instead if it doesn't fit
+/// on the terminal.
+pub fn page(out: &mut dyn Write, text: &str, enabled: bool) -> Result<()> {
+    if !enabled || !needs_paging(text) {
+        writeln!(out, "{}", text)?;
+        return Ok(());
+    }
+
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    // fall back to printing if the pager can't be started
+    let Ok(mut child) = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        writeln!(out, "{}", text)?;
+        return Ok(());
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager closes its input if the user quits early
+        match writeln!(stdin, "{}", text) {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+            result => result.context("Failed to write to pager")?,
+        }
+    }
+
+    child.wait().context("Failed to wait for pager")?;
+    Ok(())
+}
+
+fn needs_paging(text: &str) -> bool {
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+
+    match terminal_size() {
+        Some((_, Height(height))) => text.lines().count() >= height as usize,
+        None => false,
+    }
+}
@@ -0,0 +1,1321 @@
+use anyhow::Result;
+use colored::Colorize;
+use harper::{
+    blocklist, bottleneck, budget, cache_control, chunked, comments, compression, connections,
+    cookie_audit, cookie_flow, cookie_stats, cors, count_schemes, count_urls, counts,
+    critical_path, devtools_summary, dns, dns_timings, domain_lists, duplicate_headers, find_json,
+    fmt, gzippable, hashes, host_mimes, host_timings, https_audit, initiators, list_domains,
+    long_query, mime_mismatch, no_cache, ports, private_ips, rate_limit, redirects, retries,
+    search_for, serialization, size_ratio, soft_errors, third_party, ttfb, url_check, validate,
+    where_param,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+    io::{self, Write},
+};
+
+use crate::SortBy;
+
+/// Write a result as pretty printed JSON when `json` is set, otherwise as text.
+pub fn emit<T, F>(out: &mut dyn Write, json: bool, value: &T, text: F) -> Result<()>
+where
+    T: Serialize + ?Sized,
+    F: FnOnce(&mut dyn Write, &T) -> io::Result<()>,
+{
+    if json {
+        serde_json::to_writer_pretty(&mut *out, value)?;
+        writeln!(out)?;
+    } else {
+        text(out, value)?;
+    }
+
+    Ok(())
+}
+
+pub fn domain_tree(
+    out: &mut dyn Write,
+    tree: &count_urls::DomainNode,
+    sort: &SortBy,
+    flat: bool,
+) -> io::Result<()> {
+    let path = &mut Vec::new();
+    match sort {
+        SortBy::Alpha => tree_level(out, &tree.children, path, flat, &mut |(name, _)| {
+            name.to_string()
+        }),
+        SortBy::Frequency => tree_level(out, &tree.children, path, flat, &mut |(name, node)| {
+            (Reverse(node.count), name.to_string())
+        }),
+        SortBy::DomainLength => tree_level(out, &tree.children, path, flat, &mut |(name, node)| {
+            (Reverse(node.depth()), name.to_string())
+        }),
+    }
+}
+
+/// Print a level of the tree, or with `flat` only its leaves, each as a dotted
+/// name made from `path`, the names of its ancestors.
+fn tree_level<'a, F, K>(
+    out: &mut dyn Write,
+    children: &'a BTreeMap<String, count_urls::DomainNode>,
+    path: &mut Vec<&'a str>,
+    flat: bool,
+    sort_closure: &mut F,
+) -> io::Result<()>
+where
+    F: FnMut(&(&String, &count_urls::DomainNode)) -> K,
+    K: Ord,
+{
+    // get entries as a vector
+    let mut entries: Vec<_> = children.iter().collect();
+    // sort them
+    entries.sort_by_key(|e| sort_closure(e));
+
+    // iterate through entries
+    for (key, node) in entries {
+        // print each entry
+        if !flat {
+            let indent = "    ".repeat(path.len());
+            writeln!(out, "{}{} ({})", indent, key, node.count)?;
+        } else if node.children.is_empty() {
+            // the tree goes from the tld down, the empty name is a bare domain
+            let name: Vec<&str> = std::iter::once(key.as_str())
+                .chain(path.iter().rev().copied())
+                .filter(|part| !part.is_empty())
+                .collect();
+            writeln!(out, "{}: {}", name.join("."), node.count)?;
+        }
+
+        // print its children
+        path.push(key);
+        tree_level(out, &node.children, path, flat, sort_closure)?;
+        path.pop();
+    }
+
+    Ok(())
+}
+
+pub fn lines(out: &mut dyn Write, lines: &[String]) -> io::Result<()> {
+    for line in lines {
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+pub fn scheme_counts(out: &mut dyn Write, counts: &count_schemes::SchemeCounts) -> io::Result<()> {
+    for (scheme, count) in counts::by_frequency(counts) {
+        writeln!(out, "{}: {}", scheme, count)?;
+    }
+    Ok(())
+}
+
+pub fn request_count(out: &mut dyn Write, count: &usize) -> io::Result<()> {
+    writeln!(out, "Found {} requests.", count)
+}
+
+/// Everything found by a search, for JSON output.
+#[derive(Serialize, JsonSchema)]
+pub struct SearchOutput<'a> {
+    pub needles: Vec<String>,
+    pub matches: Vec<search_for::SearchResult<'a>>,
+    pub base64_matches: Vec<search_for::SearchResult<'a>>,
+    pub fuzzy_matches: Vec<search_for::FuzzyMatch>,
+}
+
+pub fn search_results(
+    out: &mut dyn Write,
+    search: &SearchOutput,
+    format: Option<&search_for::ResultTemplate>,
+) -> io::Result<()> {
+    let mut print_result = |heading: &str, result: &search_for::SearchResult| -> io::Result<()> {
+        if let Some(template) = format {
+            return writeln!(out, "{}", template.render(result));
+        }
+
+        writeln!(out, "{} {}:", heading, result.request_num)?;
+        writeln!(
+            out,
+            "Time: {}\nURL: {}\nMethod: {}",
+            result.time, result.url, result.method
+        )?;
+        // which string matched is only news when there's more than one
+        if search.needles.len() > 1 {
+            writeln!(out, "Matched: {:?}", result.needles)?;
+        }
+        writeln!(out, "In fields: {:?}\n", result.in_fields)
+    };
+
+    for result in &search.matches {
+        print_result("Found in request", result)?;
+    }
+
+    for result in &search.base64_matches {
+        print_result("Found base64 encoded in request", result)?;
+    }
+
+    for result in &search.fuzzy_matches {
+        if search.needles.len() > 1 {
+            writeln!(
+                out,
+                "Fuzzy match for {} on host {} (distance {})",
+                result.needle, result.host, result.distance
+            )?;
+        } else {
+            writeln!(
+                out,
+                "Fuzzy match on host {} (distance {})",
+                result.host, result.distance
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn search_missing(
+    out: &mut dyn Write,
+    missing: &[search_for::SearchResult],
+    format: Option<&search_for::ResultTemplate>,
+) -> io::Result<()> {
+    for result in missing {
+        if let Some(template) = format {
+            writeln!(out, "{}", template.render(result))?;
+            continue;
+        }
+
+        writeln!(out, "Not found in request {}:", result.request_num)?;
+        writeln!(
+            out,
+            "Time: {}\nURL: {}\nMethod: {}\n",
+            result.time, result.url, result.method
+        )?;
+    }
+    Ok(())
+}
+
+pub fn dnssec(out: &mut dyn Write, results: &[dns::DnssecResult]) -> io::Result<()> {
+    for result in results {
+        let status = match result.status {
+            dns::DnssecStatus::Signed => "Signature found.".green(),
+            dns::DnssecStatus::Unsigned => "No signature found.".yellow(),
+            dns::DnssecStatus::LookupFailed => "DNS lookup failed".red(),
+        };
+        writeln!(out, "{}: {}", result.domain.bold(), status)?;
+    }
+    Ok(())
+}
+
+pub fn dnssec_summary(out: &mut dyn Write, summary: &dns::DnssecSummary) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}/{} domains use DNSSEC, {} lookups failed",
+        summary.signed, summary.domains, summary.lookup_failed
+    )
+}
+
+pub fn dns_records(out: &mut dyn Write, results: &[dns::DomainRecords]) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "{}:", result.domain.bold().blue())?;
+
+        for record in &result.records {
+            writeln!(
+                out,
+                "[{:6}] {} - TTL: {} - {}",
+                record.record_type.purple().bold(),
+                record.name.cyan(),
+                record.ttl.to_string().yellow(),
+                record.data
+            )?;
+        }
+
+        if result.records.is_empty() {
+            writeln!(out, "{}", "No DNS records found".red())?;
+        }
+
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn blocklist_matches(
+    out: &mut dyn Write,
+    results: &[blocklist::BlocklistMatches],
+) -> io::Result<()> {
+    for result in results {
+        writeln!(
+            out,
+            "{}: {}",
+            "Checking blocklist".blue().bold(),
+            result.blocklist
+        )?;
+        for domain in &result.domains {
+            writeln!(out, "{}: {}", "Found".yellow(), domain.red())?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn param_matches(out: &mut dyn Write, matches: &[where_param::ParamMatch]) -> io::Result<()> {
+    for result in matches {
+        writeln!(out, "Found in request {}:", result.request_num)?;
+        writeln!(out, "URL: {}\nValue: {}\n", result.url, result.value)?;
+    }
+    Ok(())
+}
+
+pub fn bloat(
+    out: &mut dyn Write,
+    results: &[harper::bloat::BloatResult],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "Bloat in request {}:", result.request_num)?;
+        writeln!(
+            out,
+            "URL: {}\nHeader size: {}\nCookie size: {}\n",
+            result.url,
+            units.bytes(result.header_size),
+            units.bytes(result.cookie_size)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn connection_stats(
+    out: &mut dyn Write,
+    stats: &connections::ConnectionStats,
+) -> io::Result<()> {
+    for (connection, count) in counts::by_frequency(&stats.connections) {
+        writeln!(out, "Connection {}: {} requests", connection, count)?;
+    }
+
+    writeln!(
+        out,
+        "\nOpened {} connections, {} requests reused a connection.",
+        stats.opened(),
+        stats.reused()
+    )?;
+    if stats.unknown > 0 {
+        writeln!(out, "{} requests had no connection id.", stats.unknown)?;
+    }
+    Ok(())
+}
+
+pub fn private_ips(
+    out: &mut dyn Write,
+    results: &[private_ips::PrivateIpResult],
+) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "Private address in request {}:", result.request_num)?;
+        writeln!(
+            out,
+            "URL: {}\nIP: {}\nRange: {}\n",
+            result.url, result.ip, result.range
+        )?;
+    }
+    Ok(())
+}
+
+pub fn host_timings(
+    out: &mut dyn Write,
+    results: &[host_timings::HostTiming],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for result in results {
+        writeln!(
+            out,
+            "{}: {} requests, total {}, mean {}, median {}",
+            result.host.bold(),
+            result.stats.count,
+            units.duration_ms(result.stats.total),
+            units.duration_ms(result.stats.mean),
+            units.duration_ms(result.stats.median)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn mime_mismatches(
+    out: &mut dyn Write,
+    results: &[mime_mismatch::MimeMismatch],
+) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "Mime type mismatch in request {}:", result.request_num)?;
+        writeln!(
+            out,
+            "URL: {}\nExpected: {}\nActual: {}\nStatus: {}\n",
+            result.url,
+            result.expected,
+            result.actual,
+            fmt::status(result.status)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn json_matches(out: &mut dyn Write, matches: &[find_json::JsonMatch]) -> io::Result<()> {
+    for result in matches {
+        writeln!(
+            out,
+            "Found in {} body of request {}:",
+            result.body, result.request_num
+        )?;
+        writeln!(
+            out,
+            "URL: {}\nPath: {}\nValue: {}\n",
+            result.url, result.path, result.value
+        )?;
+    }
+    Ok(())
+}
+
+pub fn cookie_flows(
+    out: &mut dyn Write,
+    flows: &BTreeMap<String, cookie_flow::CookieFlow>,
+) -> io::Result<()> {
+    let mut flows_vec: Vec<_> = flows.iter().collect();
+    flows_vec.sort_by_key(|a| a.0);
+
+    for (name, flow) in flows_vec {
+        let note = if flow.never_used() {
+            " (set but never sent)".yellow().to_string()
+        } else if flow.never_set() {
+            " (sent but never set)".yellow().to_string()
+        } else {
+            String::new()
+        };
+
+        writeln!(
+            out,
+            "{}: set {}, sent {}{}",
+            name.bold(),
+            flow.set,
+            flow.sent,
+            note
+        )?;
+    }
+    Ok(())
+}
+
+pub fn urls(out: &mut dyn Write, urls: &[(String, usize)], count: bool) -> io::Result<()> {
+    for (url, n) in urls {
+        if count {
+            writeln!(out, "{:>7} {}", n, url)?;
+        } else {
+            writeln!(out, "{}", url)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn cookie_findings(out: &mut dyn Write, findings: &[cookie_audit::Finding]) -> io::Result<()> {
+    for finding in findings {
+        match finding {
+            cookie_audit::Finding::Overwrite {
+                cookie,
+                page,
+                values,
+            } => {
+                writeln!(
+                    out,
+                    "{}: {} set with conflicting values on page {}",
+                    "Overwrite".yellow().bold(),
+                    cookie.bold(),
+                    page
+                )?;
+                writeln!(out, "Values: {}\n", values.join(" -> "))?;
+            }
+            cookie_audit::Finding::LongExpiry {
+                cookie,
+                host,
+                expiry,
+                days,
+            } => {
+                writeln!(
+                    out,
+                    "{}: {} set by {} lasts {} days",
+                    "Long expiry".yellow().bold(),
+                    cookie.bold(),
+                    host,
+                    days
+                )?;
+                writeln!(out, "Expiry: {}\n", expiry)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Redirect chains, with live verification results if requested.
+#[derive(Serialize, JsonSchema)]
+pub struct RedirectsOutput {
+    pub chains: Vec<redirects::RedirectChain>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verifications: Option<Vec<redirects::Verification>>,
+}
+
+pub fn redirects(out: &mut dyn Write, output: &RedirectsOutput, list: bool) -> io::Result<()> {
+    let chains = &output.chains;
+
+    if list {
+        for chain in chains {
+            let status = chain
+                .final_status
+                .map_or("not captured".normal(), fmt::status);
+            writeln!(
+                out,
+                "Chain from request {} ({}, {} hops, final status {}):",
+                chain.hops[0],
+                chain.kind,
+                chain.hops.len(),
+                status
+            )?;
+            writeln!(out, "{}\n", chain.urls.join("\n -> "))?;
+        }
+    }
+
+    let mut kinds: HashMap<redirects::RedirectKind, usize> = HashMap::new();
+    for chain in chains {
+        *kinds.entry(chain.kind).or_insert(0) += 1;
+    }
+    let mut kinds_vec: Vec<_> = kinds.into_iter().collect();
+    kinds_vec.sort();
+
+    writeln!(out, "Found {} redirect chains.", chains.len())?;
+    for (label, permanent) in [("Permanent", true), ("Temporary", false)] {
+        let total: usize = kinds_vec
+            .iter()
+            .filter(|(kind, _)| kind.is_permanent() == permanent)
+            .map(|(_, count)| count)
+            .sum();
+        writeln!(out, "{}: {}", label.bold(), total)?;
+
+        for (kind, count) in kinds_vec
+            .iter()
+            .filter(|(k, _)| k.is_permanent() == permanent)
+        {
+            writeln!(out, "    {}: {}", kind, count)?;
+        }
+    }
+
+    let errors = chains.iter().filter(|c| c.ended_in_error()).count();
+    let incomplete = chains.iter().filter(|c| c.final_status.is_none()).count();
+    writeln!(out, "Ended in an error: {}", errors)?;
+    writeln!(out, "Final target not in capture: {}", incomplete)?;
+
+    if let Some(verifications) = &output.verifications {
+        writeln!(out)?;
+        for verification in verifications {
+            let recorded = verification
+                .recorded
+                .map_or("not captured".to_string(), |s| s.to_string());
+            let outcome = match &verification.outcome {
+                redirects::VerifyOutcome::Status(status) if verification.unchanged() => {
+                    format!("still {}", status).green()
+                }
+                redirects::VerifyOutcome::Status(status) => {
+                    format!("recorded {}, now {}", recorded, status).yellow()
+                }
+                redirects::VerifyOutcome::Failed(e) => format!("failed: {}", e).red(),
+                redirects::VerifyOutcome::Skipped => "skipped, not HTTP".normal(),
+            };
+            writeln!(out, "{}: {}", verification.url.bold(), outcome)?;
+        }
+
+        let unchanged = verifications.iter().filter(|v| v.unchanged()).count();
+        writeln!(
+            out,
+            "{} of {} final targets unchanged.",
+            unchanged,
+            verifications.len()
+        )?;
+    }
+    Ok(())
+}
+
+pub fn compression(
+    out: &mut dyn Write,
+    results: &BTreeMap<String, compression::MimeCompression>,
+    units: fmt::Units,
+) -> io::Result<()> {
+    let mut results_vec: Vec<_> = results.iter().collect();
+    results_vec.sort_by_key(|(mime_type, result)| (Reverse(result.transferred), *mime_type));
+
+    for (mime_type, result) in results_vec {
+        let ratio = result
+            .ratio()
+            .map_or("n/a".to_string(), |r| format!("{:.2}", r));
+        let flag = if result.uncompressed() {
+            " (not compressed)".yellow().to_string()
+        } else {
+            String::new()
+        };
+
+        writeln!(
+            out,
+            "{}: transferred {} (+{} headers), decoded {}, ratio {}{}",
+            mime_type.bold(),
+            units.bytes(result.transferred),
+            units.bytes(result.headers),
+            units.bytes(result.decoded),
+            ratio,
+            flag
+        )?;
+        if result.unknown > 0 {
+            writeln!(out, "    {} entries with unknown sizes", result.unknown)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn soft_errors(out: &mut dyn Write, results: &[soft_errors::SoftError]) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "Soft error in request {}:", result.request_num)?;
+        writeln!(out, "URL: {}\nMarker: {}\n", result.url, result.marker)?;
+    }
+    Ok(())
+}
+
+pub fn devtools_summary(
+    out: &mut dyn Write,
+    summary: &devtools_summary::DevtoolsSummary,
+    units: fmt::Units,
+) -> io::Result<()> {
+    let duration = |ms: Option<f64>| ms.map_or("n/a".to_string(), |ms| units.duration_ms(ms));
+
+    writeln!(
+        out,
+        "{} requests | {} transferred | {} resources | Finish: {} | DOMContentLoaded: {} | Load: {}",
+        summary.requests,
+        units.bytes(summary.transferred),
+        units.bytes(summary.resources),
+        duration(summary.finish),
+        duration(summary.dom_content_loaded),
+        duration(summary.load)
+    )
+}
+
+pub fn rate(
+    out: &mut dyn Write,
+    series: &[(chrono::DateTime<chrono::Utc>, usize)],
+    sparkline: bool,
+) -> io::Result<()> {
+    for (time, count) in series {
+        writeln!(out, "{}  {}", time.format("%Y-%m-%d %H:%M:%S%.3f"), count)?;
+    }
+
+    if sparkline {
+        let counts: Vec<usize> = series.iter().map(|(_, count)| *count).collect();
+        writeln!(out, "\n{}", harper::rate::sparkline(&counts))?;
+    }
+    Ok(())
+}
+
+pub fn chunked(out: &mut dyn Write, results: &[chunked::ChunkedResult]) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "Streaming response in request {}:", result.request_num)?;
+        writeln!(
+            out,
+            "URL: {}\nStatus: {}\nReason: {}\n",
+            result.url,
+            fmt::status(result.status),
+            result.reason
+        )?;
+    }
+    Ok(())
+}
+
+pub fn cookie_stats(
+    out: &mut dyn Write,
+    stats: &cookie_stats::CookieStats,
+    units: fmt::Units,
+) -> io::Result<()> {
+    writeln!(out, "Requests with cookies: {}", stats.with_cookies)?;
+    writeln!(out, "Requests without cookies: {}", stats.without_cookies)?;
+    writeln!(out, "Mean cookies per request: {:.2}", stats.mean_cookies)?;
+
+    if let Some(largest) = &stats.largest {
+        writeln!(
+            out,
+            "Largest cookie header: {} in request {} ({})",
+            units.bytes(largest.size as u64),
+            largest.request_num,
+            largest.url
+        )?;
+    }
+    Ok(())
+}
+
+pub fn cache_control(
+    out: &mut dyn Write,
+    stats: &cache_control::CacheControlStats,
+) -> io::Result<()> {
+    let mut directives_vec: Vec<_> = stats.directives.iter().collect();
+    directives_vec.sort_by_key(|(name, count)| (Reverse(**count), *name));
+
+    writeln!(
+        out,
+        "{} responses with Cache-Control, {} without.",
+        stats.with_header, stats.without_header
+    )?;
+    for (name, count) in directives_vec {
+        writeln!(out, "{}: {}", name.bold(), count)?;
+    }
+
+    if !stats.max_ages.is_empty() {
+        writeln!(out, "\n{}", "max-age distribution:".bold())?;
+        for (ttl, count) in &stats.max_ages {
+            writeln!(out, "    {}s: {}", ttl, count)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn ports(out: &mut dyn Write, usages: &[ports::PortUsage]) -> io::Result<()> {
+    for usage in usages {
+        writeln!(out, "{}:{}: {}", usage.host, usage.port, usage.count)?;
+    }
+
+    let total: usize = usages.iter().map(|u| u.count).sum();
+    writeln!(out, "{} requests to non-standard ports.", total)
+}
+
+pub fn ttfb(
+    out: &mut dyn Write,
+    results: &[ttfb::TtfbResult],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for result in results {
+        writeln!(
+            out,
+            "{:>10}  request {}: {} {}",
+            units.duration_ms(result.ttfb),
+            result.request_num,
+            result.method,
+            result.url
+        )?;
+    }
+    Ok(())
+}
+
+pub fn duplicate_headers(
+    out: &mut dyn Write,
+    results: &[duplicate_headers::DuplicateHeader],
+) -> io::Result<()> {
+    for result in results {
+        writeln!(
+            out,
+            "Duplicate {} header in request {}:",
+            result.side, result.request_num
+        )?;
+        writeln!(
+            out,
+            "URL: {}\nHeader: {}\nValues: {}\n",
+            result.url,
+            result.name,
+            result.values.join(" | ")
+        )?;
+    }
+    Ok(())
+}
+
+pub fn budget(
+    out: &mut dyn Write,
+    checks: &[budget::BudgetCheck],
+    budget: u64,
+    units: fmt::Units,
+) -> io::Result<()> {
+    for check in checks {
+        let status = match check.overage {
+            None => "PASS".green().bold(),
+            Some(_) => "FAIL".red().bold(),
+        };
+        write!(
+            out,
+            "{} {}: {} of {}",
+            status,
+            check.name,
+            units.bytes(check.transferred),
+            units.bytes(budget)
+        )?;
+        match check.overage {
+            Some(over) => writeln!(out, ", {} over", units.bytes(over))?,
+            None => writeln!(out)?,
+        }
+    }
+    Ok(())
+}
+
+pub fn comments(out: &mut dyn Write, comments: &[comments::Comment]) -> io::Result<()> {
+    for comment in comments {
+        match comment.request_num {
+            Some(num) => writeln!(out, "{} of request {}:", comment.location.bold(), num)?,
+            None => writeln!(out, "{}:", comment.location.bold())?,
+        }
+        writeln!(out, "{}\n", comment.text)?;
+    }
+    Ok(())
+}
+
+pub fn timing_issues(
+    out: &mut dyn Write,
+    issues: &[validate::TimingIssue],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for issue in issues {
+        writeln!(out, "Timing problem in request {}:", issue.request_num)?;
+        writeln!(out, "URL: {}", issue.url)?;
+        writeln!(out, "{}\n", timing_problem(&issue.problem, units))?;
+    }
+    Ok(())
+}
+
+fn timing_problem(problem: &validate::TimingProblem, units: fmt::Units) -> String {
+    match problem {
+        validate::TimingProblem::SumExceedsTime { sum, time } => format!(
+            "Phases sum to {} but the total time is {}",
+            units.duration_ms(*sum),
+            units.duration_ms(*time)
+        ),
+        validate::TimingProblem::Negative { phase, value } => {
+            format!("Negative {} time: {}", phase, value)
+        }
+    }
+}
+
+pub fn host_mimes(out: &mut dyn Write, results: &[host_mimes::HostMimes]) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "{} ({} requests):", result.host, result.requests)?;
+        for mime_count in &result.mime_types {
+            writeln!(out, "  {}: {}", mime_count.mime_type, mime_count.count)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn insecure_requests(
+    out: &mut dyn Write,
+    requests: &[https_audit::InsecureRequest],
+) -> io::Result<()> {
+    for request in requests {
+        writeln!(out, "Request {}: {}", request.request_num, request.url)?;
+    }
+
+    let hosts: HashSet<&str> = requests.iter().map(|r| r.host.as_str()).collect();
+    writeln!(
+        out,
+        "{} requests could use HTTPS, across {} hosts.",
+        requests.len(),
+        hosts.len()
+    )
+}
+
+pub fn long_queries(
+    out: &mut dyn Write,
+    results: &[long_query::LongQuery],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for result in results {
+        writeln!(out, "Long query in request {}:", result.request_num)?;
+        writeln!(
+            out,
+            "URL: {}\nParameters: {}\nQuery length: {}\n",
+            result.url,
+            result.param_count,
+            units.bytes(result.query_length as u64)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn cors_pairs(out: &mut dyn Write, pairs: &[cors::CorsPair]) -> io::Result<()> {
+    for pair in pairs {
+        let status = if pair.problems.is_empty() {
+            "allowed".green()
+        } else {
+            "blocked".red()
+        };
+        writeln!(
+            out,
+            "Preflight {} for request {} ({} {}): {}",
+            pair.preflight_num, pair.request_num, pair.method, pair.url, status
+        )?;
+
+        for problem in &pair.problems {
+            let (what, allowed) = match problem {
+                cors::CorsProblem::OriginNotAllowed { origin, allowed } => {
+                    (format!("Origin {}", origin), allowed)
+                }
+                cors::CorsProblem::MethodNotAllowed { method, allowed } => {
+                    (format!("Method {}", method), allowed)
+                }
+                cors::CorsProblem::HeaderNotAllowed { header, allowed } => {
+                    (format!("Header {}", header), allowed)
+                }
+            };
+            writeln!(
+                out,
+                "    {} not allowed by {}",
+                what,
+                allowed.as_deref().unwrap_or("(missing header)")
+            )?;
+        }
+    }
+
+    let blocked = pairs.iter().filter(|p| !p.problems.is_empty()).count();
+    writeln!(out, "{} preflights, {} blocked.", pairs.len(), blocked)
+}
+
+pub fn uncached_assets(out: &mut dyn Write, assets: &[no_cache::UncachedAsset]) -> io::Result<()> {
+    for asset in assets {
+        writeln!(out, "No caching headers on request {}:", asset.request_num)?;
+        writeln!(
+            out,
+            "URL: {}\nMime type: {}\nStatus: {}\n",
+            asset.url,
+            asset.mime_type,
+            fmt::status(asset.status)
+        )?;
+    }
+    writeln!(
+        out,
+        "{} static assets without caching headers.",
+        assets.len()
+    )
+}
+
+pub fn user_agents(out: &mut dyn Write, counts: &BTreeMap<String, usize>) -> io::Result<()> {
+    for (user_agent, count) in counts::by_frequency(counts) {
+        let user_agent = if user_agent.is_empty() {
+            "(no User-Agent)"
+        } else {
+            user_agent
+        };
+        writeln!(out, "{}: {}", count, user_agent)?;
+    }
+    Ok(())
+}
+
+pub fn retries(
+    out: &mut dyn Write,
+    groups: &[retries::RetryGroup],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for group in groups {
+        let outcome = if group.recovered() {
+            "recovered".green()
+        } else {
+            "still failing".red()
+        };
+        writeln!(
+            out,
+            "{} {} retried {} times, final status {} ({}):",
+            group.method,
+            group.url,
+            group.attempts() - 1,
+            fmt::status(group.final_status()),
+            outcome
+        )?;
+
+        for (i, (request_num, status)) in group.request_nums.iter().zip(&group.statuses).enumerate()
+        {
+            let gap = match i.checked_sub(1).and_then(|i| group.gaps_ms.get(i)) {
+                Some(gap) => format!(" after {}", units.duration_ms(*gap)),
+                None => String::new(),
+            };
+            writeln!(
+                out,
+                "    request {}: {}{}",
+                request_num,
+                fmt::status(*status),
+                gap
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn serialization(
+    out: &mut dyn Write,
+    serialization: &serialization::Serialization,
+    units: fmt::Units,
+) -> io::Result<()> {
+    for run in &serialization.runs {
+        let requests: Vec<String> = run.request_nums.iter().map(|n| n.to_string()).collect();
+        writeln!(
+            out,
+            "connection {}: requests {} ran one after another over {} ({} idle), parallel could save {}",
+            run.connection,
+            requests.join(", "),
+            units.duration_ms(run.span_ms),
+            units.duration_ms(run.idle_ms),
+            units.duration_ms(run.savings_ms).yellow()
+        )?;
+    }
+    writeln!(
+        out,
+        "{} serial runs, {} requests overlapped others on their connection.",
+        serialization.runs.len(),
+        serialization.overlapping
+    )
+}
+
+pub fn third_parties(
+    out: &mut dyn Write,
+    domains: &[third_party::PartyDomain],
+    site: Option<&str>,
+    units: fmt::Units,
+) -> io::Result<()> {
+    if let Some(site) = site {
+        writeln!(out, "Site: {}", site)?;
+    }
+
+    let mut counts: HashMap<domain_lists::Party, usize> = HashMap::new();
+    for domain in domains {
+        *counts.entry(domain.party).or_insert(0) += 1;
+        let party = match domain.party {
+            domain_lists::Party::First => "first".green(),
+            domain_lists::Party::Tracker => "tracker".red(),
+            domain_lists::Party::Third => "third".yellow(),
+        };
+        writeln!(
+            out,
+            "{:>7} {:>5} requests {:>10}  {}",
+            party,
+            domain.requests,
+            units.bytes(domain.bytes),
+            domain.domain
+        )?;
+    }
+
+    let count = |party| counts.get(&party).copied().unwrap_or(0);
+    writeln!(
+        out,
+        "{} first party, {} third party and {} tracker domains.",
+        count(domain_lists::Party::First),
+        count(domain_lists::Party::Third),
+        count(domain_lists::Party::Tracker)
+    )
+}
+
+pub fn gzippable(
+    out: &mut dyn Write,
+    results: &[gzippable::Uncompressed],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for result in results {
+        writeln!(
+            out,
+            "{}: {} ({}, {})",
+            result.request_num,
+            result.url,
+            result.mime_type,
+            units.bytes(result.size)
+        )?;
+    }
+    writeln!(
+        out,
+        "{} uncompressed responses, {} in total.",
+        results.len(),
+        units.bytes(results.iter().map(|result| result.size).sum())
+    )
+}
+
+pub fn size_ratios(
+    out: &mut dyn Write,
+    ratios: &size_ratio::SizeRatios,
+    units: fmt::Units,
+) -> io::Result<()> {
+    for ratio in &ratios.flagged {
+        let kind = match ratio.kind {
+            size_ratio::RatioKind::Amplified => "amplified".red(),
+            size_ratio::RatioKind::Shrunk => "shrunk".yellow(),
+        };
+        writeln!(
+            out,
+            "{}: {} {} sent {}, received {} ({:.1}x, {})",
+            ratio.request_num,
+            ratio.method,
+            ratio.url,
+            units.bytes(ratio.request_bytes),
+            units.bytes(ratio.response_bytes),
+            ratio.ratio,
+            kind
+        )?;
+    }
+
+    match ratios.median_ratio {
+        Some(median) => writeln!(
+            out,
+            "{} of {} requests flagged, median response to request ratio {:.2}x.",
+            ratios.flagged.len(),
+            ratios.measured,
+            median
+        ),
+        None => writeln!(out, "No requests with both sizes recorded."),
+    }
+}
+
+pub fn critical_paths(
+    out: &mut dyn Write,
+    paths: &[critical_path::CriticalPath],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for path in paths {
+        let page = match (&path.pageref, &path.title) {
+            (Some(pageref), Some(title)) => format!("{} ({})", pageref, title),
+            (Some(pageref), None) => pageref.clone(),
+            (None, _) => "capture".to_string(),
+        };
+        match path.on_load_ms {
+            Some(on_load) => writeln!(out, "{}, onLoad at {}:", page, units.duration_ms(on_load))?,
+            None => writeln!(out, "{}:", page)?,
+        }
+
+        for step in &path.steps {
+            let link = match step.link {
+                critical_path::Link::Start => "",
+                critical_path::Link::Redirect => " (redirected)",
+                critical_path::Link::Referer => " (from referer)",
+                critical_path::Link::Preceding => "",
+            };
+            writeln!(
+                out,
+                "    {:>10} {:>10}  {}: {}{}",
+                units.duration_ms(step.end_ms),
+                format!("+{}", units.duration_ms(step.duration_ms)),
+                step.request_num,
+                step.url,
+                link.dimmed()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn dns_timings(
+    out: &mut dyn Write,
+    results: &[dns_timings::DnsTiming],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for result in results {
+        let host = if result.slow {
+            result.host.red().bold()
+        } else {
+            result.host.bold()
+        };
+        writeln!(
+            out,
+            "{}: {} lookups in {} requests, median {}, mean {}, max {}",
+            host,
+            result.lookups.count,
+            result.requests,
+            units.duration_ms(result.lookups.median),
+            units.duration_ms(result.lookups.mean),
+            units.duration_ms(result.max_ms)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn malformed_entries(
+    out: &mut dyn Write,
+    entries: &[validate::MalformedEntry],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for entry in entries {
+        writeln!(out, "Malformed request {}:", entry.request_num)?;
+        writeln!(out, "URL: {}", entry.url)?;
+        for problem in &entry.problems {
+            let description = match problem {
+                validate::EntryProblem::Missing { field } => format!("Missing {}", field),
+                validate::EntryProblem::InvalidDate { value } => {
+                    format!("Invalid startedDateTime: {}", value)
+                }
+                validate::EntryProblem::UnknownPage { pageref } => {
+                    format!("Unknown pageref: {}", pageref)
+                }
+                validate::EntryProblem::Timing { problem } => timing_problem(problem, units),
+            };
+            writeln!(out, "  - {}", description)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+pub fn initiators(out: &mut dyn Write, initiators: &initiators::Initiators) -> io::Result<()> {
+    if initiators.kinds.is_empty() {
+        return writeln!(out, "No requests record an initiator.");
+    }
+
+    for kind in &initiators.kinds {
+        writeln!(out, "{}: {} requests", kind.kind.bold(), kind.requests)?;
+    }
+    if initiators.skipped > 0 {
+        writeln!(
+            out,
+            "{}",
+            format!("{} requests without an initiator", initiators.skipped).dimmed()
+        )?;
+    }
+
+    if !initiators.scripts.is_empty() {
+        writeln!(out, "\nTriggering scripts:")?;
+        for script in &initiators.scripts {
+            writeln!(out, "  {:>5}  {}", script.requests, script.url)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn body_hashes(
+    out: &mut dyn Write,
+    hashes: &[hashes::BodyHash],
+    units: fmt::Units,
+) -> io::Result<()> {
+    for body in hashes {
+        writeln!(
+            out,
+            "{:>4}  {}  {:>10}  {}",
+            body.request_num,
+            body.hash,
+            units.bytes(body.size),
+            body.url
+        )?;
+    }
+    Ok(())
+}
+
+pub fn duplicate_bodies(
+    out: &mut dyn Write,
+    duplicates: &[hashes::DuplicateBody],
+    units: fmt::Units,
+) -> io::Result<()> {
+    if duplicates.is_empty() {
+        return writeln!(out, "No identical bodies served from different URLs.");
+    }
+
+    for duplicate in duplicates {
+        writeln!(
+            out,
+            "{} ({}, {} redundant):",
+            duplicate.hash.bold(),
+            units.bytes(duplicate.size),
+            units.bytes(duplicate.redundant_bytes())
+        )?;
+        for url in &duplicate.urls {
+            writeln!(out, "  {}", url)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn bottlenecks(
+    out: &mut dyn Write,
+    bottlenecks: &bottleneck::Bottlenecks,
+    list_requests: bool,
+    units: fmt::Units,
+) -> io::Result<()> {
+    if list_requests {
+        for request in &bottlenecks.requests {
+            writeln!(
+                out,
+                "{:>4}  {:<8} {:>10} of {:>10}  {}",
+                request.request_num,
+                request.phase.name(),
+                units.duration_ms(request.phase_ms),
+                units.duration_ms(request.time_ms),
+                request.url
+            )?;
+        }
+        if !bottlenecks.requests.is_empty() {
+            writeln!(out)?;
+        }
+    }
+
+    if bottlenecks.phases.is_empty() {
+        return writeln!(out, "No requests record timings.");
+    }
+
+    for count in &bottlenecks.phases {
+        writeln!(
+            out,
+            "{} requests were dominated by {}, {} in total",
+            count.requests,
+            count.phase.name().bold(),
+            units.duration_ms(count.total_ms)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn malformed_urls(
+    out: &mut dyn Write,
+    malformed: &[url_check::MalformedUrl],
+) -> io::Result<()> {
+    for url in malformed {
+        // escaped so control characters can't mangle the terminal
+        writeln!(
+            out,
+            "Request {}: {}",
+            url.request_num,
+            url.url.escape_debug()
+        )?;
+        for problem in &url.problems {
+            let description = match problem {
+                url_check::UrlProblem::Unparseable { error } => format!("Doesn't parse: {}", error),
+                url_check::UrlProblem::MissingHost => "No host".to_string(),
+                url_check::UrlProblem::Whitespace => "Contains whitespace".to_string(),
+                url_check::UrlProblem::ControlCharacters => {
+                    "Contains control characters".to_string()
+                }
+            };
+            writeln!(out, "  - {}", description)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn domain_counts(out: &mut dyn Write, counts: &[list_domains::DomainCount]) -> io::Result<()> {
+    for count in counts {
+        writeln!(out, "{}: {}", count.domain, count.requests)?;
+    }
+    Ok(())
+}
+
+pub fn rate_limits(out: &mut dyn Write, limits: &[rate_limit::HostRateLimit]) -> io::Result<()> {
+    if limits.is_empty() {
+        return writeln!(out, "No requests were rate limited.");
+    }
+
+    for limit in limits {
+        writeln!(
+            out,
+            "{}: {} of {} requests rate limited",
+            limit.host.bold(),
+            limit.limited.len(),
+            limit.requests
+        )?;
+        // a limit over a longer period than the window can let a higher rate through
+        match limit
+            .allowed_up_to_rps
+            .filter(|&allowed| allowed < limit.limited_at_rps)
+        {
+            Some(allowed) => writeln!(
+                out,
+                "  Limit between {:.1} and {:.1} requests/s",
+                allowed, limit.limited_at_rps
+            )?,
+            None => writeln!(out, "  Limited at {:.1} requests/s", limit.limited_at_rps)?,
+        }
+        if let Some(retry_after) = &limit.retry_after {
+            writeln!(out, "  Retry-After: {}", retry_after)?;
+        }
+        let requests: Vec<String> = limit.limited.iter().map(|n| n.to_string()).collect();
+        writeln!(out, "  Requests: {}", requests.join(", "))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use schemars::{schema_for, Schema};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use harper::{
+    bloat, blocklist, bottleneck, budget, cache_control, chunked, comments, compression,
+    connections, cookie_audit, cookie_flow, cookie_stats, cors, count_schemes, count_urls,
+    critical_path, devtools_summary, dns, dns_timings, duplicate_headers, find_json, gzippable,
+    hashes, host_mimes, host_timings, https_audit, initiators, list_domains, long_query,
+    mime_mismatch, no_cache, ports, private_ips, rate_limit, retries, search_for, serialization,
+    size_ratio, soft_errors, third_party, ttfb, url_check, validate, where_param,
+};
+
+use crate::render;
+
+/// Schemas of every command's `--json` output, with the harper version they describe.
+#[derive(Serialize)]
+pub struct Schemas {
+    pub version: &'static str,
+    pub commands: BTreeMap<&'static str, Schema>,
+}
+
+/// The `--json` output schema of each command, keyed by command name. Flags
+/// that change the shape of the output get their own entry, i.e.
+/// `count-urls --total-only`.
+pub fn schemas() -> Schemas {
+    let commands = BTreeMap::from([
+        ("count-urls", schema_for!(count_urls::DomainNode)),
+        ("count-urls --total-only", schema_for!(usize)),
+        ("list-domains", schema_for!(Vec<String>)),
+        (
+            "list-domains --count",
+            schema_for!(Vec<list_domains::DomainCount>),
+        ),
+        ("count-schemes", schema_for!(count_schemes::SchemeCounts)),
+        ("count-requests", schema_for!(usize)),
+        ("search-for", schema_for!(render::SearchOutput<'static>)),
+        (
+            "search-for --invert",
+            schema_for!(Vec<search_for::SearchResult<'static>>),
+        ),
+        ("dnssec-audit", schema_for!(Vec<dns::DnssecResult>)),
+        ("dnssec-audit --list-only", schema_for!(Vec<String>)),
+        (
+            "dnssec-audit --summary-only",
+            schema_for!(dns::DnssecSummary),
+        ),
+        ("dns-lookup", schema_for!(Vec<dns::DomainRecords>)),
+        ("block-list", schema_for!(Vec<blocklist::BlocklistMatches>)),
+        ("where-param", schema_for!(Vec<where_param::ParamMatch>)),
+        ("bloat", schema_for!(Vec<bloat::BloatResult>)),
+        ("connections", schema_for!(connections::ConnectionStats)),
+        (
+            "private-ips",
+            schema_for!(Vec<private_ips::PrivateIpResult>),
+        ),
+        ("host-timings", schema_for!(Vec<host_timings::HostTiming>)),
+        (
+            "mime-mismatch",
+            schema_for!(Vec<mime_mismatch::MimeMismatch>),
+        ),
+        ("find-json", schema_for!(Vec<find_json::JsonMatch>)),
+        (
+            "cookie-flow",
+            schema_for!(BTreeMap<String, cookie_flow::CookieFlow>),
+        ),
+        ("urls", schema_for!(Vec<(String, usize)>)),
+        ("cookie-audit", schema_for!(Vec<cookie_audit::Finding>)),
+        ("ttfb", schema_for!(Vec<ttfb::TtfbResult>)),
+        ("redirects", schema_for!(render::RedirectsOutput)),
+        (
+            "compression",
+            schema_for!(BTreeMap<String, compression::MimeCompression>),
+        ),
+        ("soft-errors", schema_for!(Vec<soft_errors::SoftError>)),
+        (
+            "devtools-summary",
+            schema_for!(devtools_summary::DevtoolsSummary),
+        ),
+        ("rate", schema_for!(Vec<(DateTime<Utc>, usize)>)),
+        ("chunked", schema_for!(Vec<chunked::ChunkedResult>)),
+        ("cookie-stats", schema_for!(cookie_stats::CookieStats)),
+        (
+            "cache-control",
+            schema_for!(cache_control::CacheControlStats),
+        ),
+        ("ports", schema_for!(Vec<ports::PortUsage>)),
+        (
+            "duplicate-headers",
+            schema_for!(Vec<duplicate_headers::DuplicateHeader>),
+        ),
+        ("budget", schema_for!(Vec<budget::BudgetCheck>)),
+        ("comments", schema_for!(Vec<comments::Comment>)),
+        ("timing-check", schema_for!(Vec<validate::TimingIssue>)),
+        ("host-mimes", schema_for!(Vec<host_mimes::HostMimes>)),
+        (
+            "https-audit",
+            schema_for!(Vec<https_audit::InsecureRequest>),
+        ),
+        ("long-query", schema_for!(Vec<long_query::LongQuery>)),
+        ("cors", schema_for!(Vec<cors::CorsPair>)),
+        ("no-cache", schema_for!(Vec<no_cache::UncachedAsset>)),
+        ("user-agents", schema_for!(BTreeMap<String, usize>)),
+        ("retries", schema_for!(Vec<retries::RetryGroup>)),
+        ("serialization", schema_for!(serialization::Serialization)),
+        ("third-party", schema_for!(Vec<third_party::PartyDomain>)),
+        ("gzippable", schema_for!(Vec<gzippable::Uncompressed>)),
+        ("size-ratio", schema_for!(size_ratio::SizeRatios)),
+        (
+            "critical-path",
+            schema_for!(Vec<critical_path::CriticalPath>),
+        ),
+        ("dns-timings", schema_for!(Vec<dns_timings::DnsTiming>)),
+        ("initiators", schema_for!(initiators::Initiators)),
+        ("hashes", schema_for!(Vec<hashes::DuplicateBody>)),
+        ("hashes --all", schema_for!(Vec<hashes::BodyHash>)),
+        ("bottleneck", schema_for!(bottleneck::Bottlenecks)),
+        ("url-check", schema_for!(Vec<url_check::MalformedUrl>)),
+        ("rate-limit", schema_for!(Vec<rate_limit::HostRateLimit>)),
+    ]);
+
+    Schemas {
+        version: env!("CARGO_PKG_VERSION"),
+        commands,
+    }
+}
+
+/// The schema of a single command's `--json` output.
+pub fn schema(command: &str) -> Result<Schema> {
+    schemas().commands.remove(command).ok_or_else(|| {
+        anyhow!(
+            "No JSON schema for '{}', it doesn't exist or has no --json output",
+            command
+        )
+    })
+}
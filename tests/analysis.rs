@@ -0,0 +1,561 @@
+mod common;
+
+use harper::{
+    annotate, bloat, body, bottleneck, budget, bytes, cookie_audit, critical_path, decode,
+    dns_timings,
+    domain_lists::{DomainList, Party, PartyClassifier},
+    gzippable, hashes, https_audit, initiators, long_query, no_cache, rate, rate_limit, redirects,
+    retries, size_ratio, third_party, ttfb, validate,
+};
+use tldextract::TldOption;
+
+#[test]
+fn consistent_timings_have_no_issues() {
+    assert!(validate::timing_check(&common::load("multi_page.har")).is_empty());
+}
+
+#[test]
+fn strict_check_reports_every_problem_of_an_entry() {
+    assert!(validate::strict_check(&common::load("multi_page.har")).is_empty());
+
+    let mut har = common::load("multi_page.har");
+    har.log.entries[1].started_date_time = "yesterday".to_string();
+    har.log.entries[1].pageref = Some("page_9".to_string());
+    har.log.entries[1].timings.as_mut().unwrap().dns = Some(-5.0);
+    har.log.entries[4].response.content = None;
+
+    let malformed = validate::strict_check(&har);
+    let problems: Vec<(usize, Vec<String>)> = malformed
+        .iter()
+        .map(|entry| {
+            let kinds = entry
+                .problems
+                .iter()
+                .map(|problem| match problem {
+                    validate::EntryProblem::Missing { field } => format!("missing {}", field),
+                    validate::EntryProblem::InvalidDate { .. } => "invalid date".to_string(),
+                    validate::EntryProblem::UnknownPage { pageref } => pageref.clone(),
+                    validate::EntryProblem::Timing { .. } => "timing".to_string(),
+                })
+                .collect();
+            (entry.request_num, kinds)
+        })
+        .collect();
+    assert_eq!(
+        problems,
+        [
+            (
+                2,
+                vec![
+                    "invalid date".to_string(),
+                    "page_9".to_string(),
+                    "timing".to_string()
+                ]
+            ),
+            (5, vec!["missing response.content".to_string()]),
+        ]
+    );
+
+    let minimal = validate::strict_check(&common::load("minimal.har"));
+    assert_eq!(minimal.len(), 1);
+    assert!(minimal[0].problems.iter().any(
+        |problem| matches!(problem, validate::EntryProblem::Missing { field } if field == "timings")
+    ));
+}
+
+#[test]
+fn finds_redirect_chain() {
+    let chains = redirects::redirect_chains(&common::load("multi_page.har"));
+
+    assert_eq!(chains.len(), 1);
+    let chain = &chains[0];
+    assert_eq!(chain.hops, [6]);
+    assert_eq!(chain.kind, redirects::RedirectKind::MovedPermanently);
+    assert_eq!(chain.final_status, Some(200));
+    assert!(!chain.ended_in_error());
+}
+
+#[test]
+fn finds_http_requests_to_https_hosts() {
+    let requests = https_audit::https_audit(&common::load("multi_page.har"));
+
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].request_num, 6);
+    assert_eq!(requests[0].host, "shop.example.com");
+}
+
+#[test]
+fn finds_uncached_static_assets() {
+    let assets = no_cache::uncached_assets(&common::load("multi_page.har"));
+
+    let found: Vec<(usize, &str)> = assets
+        .iter()
+        .map(|asset| (asset.request_num, asset.mime_type.as_str()))
+        .collect();
+    assert_eq!(found, [(3, "text/css"), (9, "image/gif")]);
+}
+
+#[test]
+fn long_query_thresholds() {
+    let har = common::load("multi_page.har");
+
+    assert!(long_query::find_long_queries(&har, 512, 20).is_empty());
+
+    let results = long_query::find_long_queries(&har, 512, 1);
+    let found: Vec<(usize, usize)> = results
+        .iter()
+        .map(|result| (result.request_num, result.param_count))
+        .collect();
+    assert_eq!(found, [(5, 2), (9, 2)]);
+}
+
+#[test]
+fn session_cookie_has_no_findings() {
+    assert!(cookie_audit::cookie_audit(&common::load("multi_page.har"), 400).is_empty());
+}
+
+#[test]
+fn decodes_bodies() {
+    let har = common::load("multi_page.har");
+
+    assert_eq!(
+        body::body(&har, 5, body::BodySide::Response).unwrap(),
+        body::Body::Text(r#"{"products":[{"id":1,"name":"Widget"}]}"#.to_string())
+    );
+    assert_eq!(
+        body::body(&har, -2, body::BodySide::Request).unwrap(),
+        body::Body::Text(r#"{"product":1,"quantity":2,"coupon":"SPRING24"}"#.to_string())
+    );
+    assert!(matches!(
+        body::body(&har, 4, body::BodySide::Response).unwrap(),
+        body::Body::Binary(_)
+    ));
+    assert!(body::body(&har, 1, body::BodySide::Request).is_err());
+    assert!(body::body(&har, 10, body::BodySide::Response).is_err());
+}
+
+#[test]
+fn computes_missing_header_sizes() {
+    let har = common::load("minimal.har");
+    let entry = &har.log.entries[0];
+
+    // "GET /health HTTP/1.1" and "Accept: */*", each with CRLF, then a blank line
+    assert_eq!(bytes::request_headers_size(&entry.request), 22 + 13 + 2);
+    // "HTTP/1.1 200 OK" and "Content-Length: 2"
+    assert_eq!(bytes::response_headers_size(&entry.response), 17 + 19 + 2);
+    assert_eq!(bytes::transferred_bytes(entry), Some(2 + 38));
+}
+
+#[test]
+fn lenient_fields_parse_as_missing() {
+    let har = common::load("minimal.har");
+    let entry = &har.log.entries[0];
+
+    assert!(entry.timings.is_none());
+    assert!(entry.response.content.is_none());
+    assert!(entry.request.headers_size.is_none());
+}
+
+#[test]
+fn finds_retry_groups() {
+    let groups = retries::find_retries(&common::load("retries.har"));
+
+    assert_eq!(groups.len(), 2);
+
+    let jobs = &groups[0];
+    assert_eq!(jobs.method, "GET");
+    assert_eq!(jobs.request_nums, [1, 3, 5]);
+    assert_eq!(jobs.gaps_ms, [600.0, 1500.0]);
+    assert_eq!(jobs.final_status(), 200);
+    assert!(jobs.recovered());
+
+    let status = &groups[1];
+    assert_eq!(status.request_nums, [6, 7]);
+    assert_eq!(status.attempts(), 2);
+    assert!(!status.recovered());
+}
+
+#[test]
+fn classifies_parties_against_lists() {
+    let har = common::load("multi_page.har");
+    let extractor = TldOption::default().build();
+    let classifier = PartyClassifier::new(
+        &har,
+        &extractor,
+        DomainList::parse("# our CDN\nExample.NET\n\n"),
+        DomainList::parse("example.org\n"),
+    );
+
+    assert_eq!(classifier.site(), Some("example.com"));
+
+    let domains = third_party::third_parties(&har, &classifier);
+    let found: Vec<(&str, Party, usize)> = domains
+        .iter()
+        .map(|domain| (domain.domain.as_str(), domain.party, domain.requests))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("example.com", Party::First, 5),
+            ("example.net", Party::First, 3),
+            ("example.org", Party::Tracker, 1),
+        ]
+    );
+}
+
+#[test]
+fn finds_uncompressed_text_responses() {
+    let har = common::load("multi_page.har");
+
+    assert!(gzippable::find_gzippable(&har, 1024).is_empty());
+
+    // app.js is sent with brotli, logo.png and the pixel aren't text
+    let results = gzippable::find_gzippable(&har, 40);
+    let found: Vec<(usize, u64)> = results
+        .iter()
+        .map(|result| (result.request_num, result.size))
+        .collect();
+    assert_eq!(found, [(1, 73), (7, 68), (8, 45)]);
+}
+
+#[test]
+fn annotates_entries_keeping_original_fields() {
+    let har = common::load("multi_page.har");
+    let extractor = TldOption::default().build();
+    let classifier = PartyClassifier::new(
+        &har,
+        &extractor,
+        DomainList::default(),
+        DomainList::default(),
+    );
+
+    let mut raw = common::load_raw("multi_page.har");
+    let original = raw.clone();
+    annotate::annotate(&mut raw, &classifier);
+
+    let entries = &raw["log"]["entries"];
+    assert_eq!(entries.len(), original["log"]["entries"].len());
+    for (annotated, entry) in entries.members().zip(original["log"]["entries"].members()) {
+        let mut annotated = annotated.clone();
+        annotated.remove(annotate::ANNOTATION_KEY);
+        assert_eq!(&annotated, entry);
+    }
+
+    let first = &entries[0][annotate::ANNOTATION_KEY];
+    assert_eq!(first["party"], "first");
+    assert_eq!(first["registrable_domain"], "example.com");
+    assert_eq!(first["body_length"], 73);
+    assert_eq!(first["timing_sum"], 150);
+    assert_eq!(entries[1][annotate::ANNOTATION_KEY]["party"], "third");
+}
+
+#[test]
+fn flags_lopsided_request_and_response_sizes() {
+    let mut har = common::load("multi_page.har");
+
+    let ratios = size_ratio::size_ratios(&har, 50.0, 10240);
+    assert_eq!(ratios.measured, 9);
+    assert!(ratios.flagged.is_empty());
+
+    let ratios = size_ratio::size_ratios(&har, 1.5, 0);
+    let found: Vec<(usize, size_ratio::RatioKind)> = ratios
+        .flagged
+        .iter()
+        .map(|ratio| (ratio.request_num, ratio.kind))
+        .collect();
+    assert_eq!(
+        found,
+        [
+            (1, size_ratio::RatioKind::Amplified),
+            (7, size_ratio::RatioKind::Amplified),
+            (8, size_ratio::RatioKind::Shrunk),
+            (9, size_ratio::RatioKind::Shrunk),
+            (3, size_ratio::RatioKind::Shrunk),
+        ]
+    );
+
+    // unknown sizes are left out rather than counted as empty
+    har.log.entries[0].response.body_size = -1;
+    har.log.entries[1].request.body_size = -1;
+    let ratios = size_ratio::size_ratios(&har, 1.5, 0);
+    assert_eq!(ratios.measured, 7);
+    assert_eq!(ratios.flagged[0].request_num, 7);
+}
+
+#[test]
+fn critical_path_follows_redirects_and_preceding_resources() {
+    let har = common::load("multi_page.har");
+    let paths = critical_path::critical_paths(&har);
+
+    let chains: Vec<(Option<&str>, Vec<usize>, Vec<critical_path::Link>)> = paths
+        .iter()
+        .map(|path| {
+            (
+                path.pageref.as_deref(),
+                path.steps.iter().map(|step| step.request_num).collect(),
+                path.steps.iter().map(|step| step.link).collect(),
+            )
+        })
+        .collect();
+    use critical_path::Link::*;
+    assert_eq!(
+        chains,
+        [
+            (
+                Some("page_1"),
+                vec![1, 3, 4],
+                vec![Start, Preceding, Preceding]
+            ),
+            (
+                Some("page_2"),
+                vec![6, 7, 9],
+                vec![Start, Redirect, Preceding]
+            ),
+        ]
+    );
+
+    // every step finishes before the page's onLoad
+    for path in &paths {
+        let on_load = path.on_load_ms.unwrap();
+        assert!(path.steps.iter().all(|step| step.end_ms <= on_load));
+    }
+}
+
+#[test]
+fn critical_path_without_pages_covers_the_capture() {
+    let har = common::load("minimal.har");
+    let paths = critical_path::critical_paths(&har);
+
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].pageref, None);
+    assert_eq!(paths[0].steps.len(), 1);
+    assert_eq!(paths[0].steps[0].link, critical_path::Link::Start);
+}
+
+#[test]
+fn dns_timings_skip_unrecorded_and_cached_lookups() {
+    let mut har = common::load("multi_page.har");
+
+    let results = dns_timings::dns_timings(&har, 15.0);
+    let hosts: Vec<(&str, f64, bool)> = results
+        .iter()
+        .map(|result| (result.host.as_str(), result.lookups.median, result.slow))
+        .collect();
+    assert_eq!(
+        hosts,
+        [
+            ("tracker.example.org", 20.0, true),
+            ("shop.example.com", 15.0, true),
+            ("api.example.com", 12.0, false),
+            ("cdn.example.net", 10.0, false),
+        ]
+    );
+
+    // a cached lookup counts as a request but not a lookup
+    har.log.entries[2].timings.as_mut().unwrap().dns = Some(0.0);
+    let results = dns_timings::dns_timings(&har, 15.0);
+    let cdn = results
+        .iter()
+        .find(|result| result.host == "cdn.example.net")
+        .unwrap();
+    assert_eq!(cdn.requests, 2);
+    assert_eq!(cdn.lookups.count, 1);
+}
+
+#[test]
+fn initiators_tally_types_and_triggering_scripts() {
+    let har = common::load("initiators.har");
+    let initiators = initiators::initiators(&har, 10);
+
+    let kinds: Vec<(&str, usize)> = initiators
+        .kinds
+        .iter()
+        .map(|kind| (kind.kind.as_str(), kind.requests))
+        .collect();
+    assert_eq!(
+        kinds,
+        [("script", 3), ("parser", 2), ("other", 1), ("preload", 1)]
+    );
+    // the plain url initiator and the entry without one
+    assert_eq!(initiators.skipped, 2);
+
+    let scripts: Vec<(&str, usize)> = initiators
+        .scripts
+        .iter()
+        .map(|script| (script.url.as_str(), script.requests))
+        .collect();
+    assert_eq!(
+        scripts,
+        [
+            ("https://example.com/app.js", 2),
+            ("https://ads.example.net/tag.js", 1)
+        ]
+    );
+
+    assert_eq!(initiators::initiators(&har, 1).scripts.len(), 1);
+    assert!(initiators::initiators(&common::load("multi_page.har"), 10)
+        .kinds
+        .is_empty());
+}
+
+#[test]
+fn hashes_find_bodies_served_from_several_urls() {
+    let mut har = common::load("multi_page.har");
+
+    let hashes = hashes::body_hashes(&har);
+    // the pixel is base64 encoded, and hashed decoded
+    let pixel = hashes.iter().find(|body| body.request_num == 9).unwrap();
+    assert_eq!(pixel.size, 14);
+    assert!(hashes::duplicates(&hashes).is_empty());
+
+    // the same stylesheet from a second host, and the first fetched again
+    let style = har.log.entries[2]
+        .response
+        .content
+        .as_ref()
+        .unwrap()
+        .text
+        .clone();
+    for i in [3, 4] {
+        har.log.entries[i].response.content.as_mut().unwrap().text = style.clone();
+        har.log.entries[i]
+            .response
+            .content
+            .as_mut()
+            .unwrap()
+            .encoding = None;
+    }
+    har.log.entries[4].request.url = har.log.entries[2].request.url.clone();
+
+    let duplicates = hashes::duplicates(&hashes::body_hashes(&har));
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].request_nums, [3, 4, 5]);
+    assert_eq!(
+        duplicates[0].urls,
+        [
+            "https://cdn.example.net/static/style.css",
+            "https://cdn.example.net/img/logo.png"
+        ]
+    );
+    assert_eq!(duplicates[0].hash.len(), 64);
+    assert_eq!(duplicates[0].redundant_bytes(), 2 * duplicates[0].size);
+}
+
+#[test]
+fn bottlenecks_count_the_slowest_phase_of_each_request() {
+    let mut har = common::load("multi_page.har");
+
+    let bottlenecks = bottleneck::bottlenecks(&har);
+    let phases: Vec<(&str, usize)> = bottlenecks
+        .phases
+        .iter()
+        .map(|count| (count.phase.name(), count.requests))
+        .collect();
+    assert_eq!(phases, [("wait", 7), ("dns", 1), ("connect", 1)]);
+    assert_eq!(bottlenecks.phases[0].total_ms, 530.0);
+
+    // a -1 phase is never the slowest, and requests without timings are left out
+    let timing = har.log.entries[8].timings.as_mut().unwrap();
+    timing.dns = Some(-1.0);
+    har.log.entries[0].timings = None;
+    let bottlenecks = bottleneck::bottlenecks(&har);
+    assert_eq!(bottlenecks.requests.len(), 8);
+    assert_ne!(
+        bottlenecks.requests.last().unwrap().phase,
+        harper::timing::Phase::Dns
+    );
+}
+
+#[test]
+fn rate_limits_bracket_the_rate_that_triggered_429s() {
+    let har = common::load("rate_limited.har");
+    let per_second: rate::Interval = "1s".parse().unwrap();
+
+    let limits = rate_limit::rate_limits(&har, per_second);
+    assert_eq!(limits.len(), 1);
+    let limit = &limits[0];
+    assert_eq!(limit.host, "api.example.com");
+    assert_eq!(limit.requests, 20);
+    assert_eq!(limit.limited, [23, 24, 25]);
+    // the eighth request of the burst within a second was the first limited
+    assert_eq!(limit.allowed_up_to_rps, Some(7.0));
+    assert_eq!(limit.limited_at_rps, 8.0);
+    assert_eq!(limit.retry_after.as_deref(), Some("30"));
+
+    assert!(rate_limit::rate_limits(&common::load("multi_page.har"), per_second).is_empty());
+}
+
+#[test]
+fn decode_falls_back_to_base64url() {
+    assert_eq!(decode::decode_base64("aGk/Pz8=").unwrap(), b"hi???");
+    assert_eq!(decode::decode_base64("aGk_Pz8").unwrap(), b"hi???");
+    // wrapped across lines
+    assert_eq!(decode::decode_base64("aGk_\nPz8").unwrap(), b"hi???");
+    assert!(decode::decode_base64("not base64!").is_none());
+
+    let har = common::load("quirks.har");
+    let content = har.log.entries[1].response.content.as_ref().unwrap();
+    assert_eq!(
+        decode::decode_body_text(content).unwrap(),
+        r#"{"items":[{"id":"a>>?","owner":{"id":7}}]}"#
+    );
+
+    let mut raw = common::load_raw("quirks.har");
+    decode::decode_bodies(&mut raw);
+    let content = &raw["log"]["entries"][1]["response"]["content"];
+    assert!(content["text"].as_str().unwrap().starts_with(r#"{"items""#));
+    assert!(!content.has_key("encoding"));
+}
+
+#[test]
+fn ttfb_sums_phases_up_to_wait_slowest_first() {
+    let results: Vec<(usize, f64)> = ttfb::ttfb(&common::load("quirks.har"))
+        .into_iter()
+        .map(|result| (result.request_num, result.ttfb))
+        .collect();
+
+    // unrecorded -1 phases count as zero, receive isn't counted
+    assert_eq!(results, [(2, 65.0), (1, 37.0), (3, 16.0)]);
+    assert!(ttfb::ttfb(&common::load("minimal.har")).is_empty());
+
+    // the 18 ms of ssl in the first request is part of its 30 ms connect
+    let results = ttfb::ttfb(&common::load("multi_page.har"));
+    let first = results.iter().find(|result| result.request_num == 1).unwrap();
+    assert_eq!(first.ttfb, 2.0 + 15.0 + 30.0 + 1.0 + 90.0);
+}
+
+#[test]
+fn budget_checks_the_capture_and_each_page() {
+    let har = common::load("quirks.har");
+
+    // headers and bodies of the responses, 150 + 1200 + 106
+    let total = budget::check_total(&har, 1456);
+    assert_eq!(total.transferred, 1456);
+    assert!(total.passed());
+    let total = budget::check_total(&har, 1000);
+    assert_eq!(total.overage, Some(456));
+    assert!(!total.passed());
+
+    // the last request isn't part of a page
+    let pages = budget::check_pages(&har, 1000);
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].name, "page_1");
+    assert_eq!(pages[0].transferred, 1350);
+    assert_eq!(pages[0].overage, Some(350));
+}
+
+#[test]
+fn bloat_compares_headers_and_cookies_to_the_threshold() {
+    let har = common::load("quirks.har");
+
+    let bloated: Vec<(usize, u64, u64)> = bloat::find_bloat(&har, 250)
+        .into_iter()
+        .map(|result| (result.request_num, result.header_size, result.cookie_size))
+        .collect();
+    // the cookie size falls back to the Cookie header without a cookie array
+    assert_eq!(bloated, [(1, 300, 0), (2, 500, 23)]);
+
+    let bloated = bloat::find_bloat(&har, 20);
+    assert_eq!(bloated.len(), 3);
+    assert!(bloat::find_bloat(&har, 500).is_empty());
+}
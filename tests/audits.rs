@@ -0,0 +1,136 @@
+mod common;
+
+use harper::{
+    chunked::{self, StreamingReason},
+    comments,
+    cors::{self, CorsProblem},
+    duplicate_headers, mime_mismatch,
+    private_ips::{self, IpRange},
+};
+use std::net::IpAddr;
+
+#[test]
+fn preflight_pairs_with_the_request_it_allows() {
+    // multi_page.har has no preflights
+    assert!(cors::cors_pairs(&common::load("multi_page.har")).is_empty());
+
+    let pairs = cors::cors_pairs(&common::load("quirks.har"));
+    assert_eq!(pairs.len(), 1);
+    let pair = &pairs[0];
+    assert_eq!((pair.preflight_num, pair.request_num), (1, 2));
+    assert_eq!(pair.method, "DELETE");
+
+    // the origin is allowed and Content-Type matches case-insensitively
+    assert_eq!(pair.problems.len(), 2);
+    assert!(matches!(
+        &pair.problems[0],
+        CorsProblem::MethodNotAllowed { method, allowed }
+            if method == "DELETE" && allowed.as_deref() == Some("GET, PUT")
+    ));
+    assert!(matches!(
+        &pair.problems[1],
+        CorsProblem::HeaderNotAllowed { header, .. } if header == "X-Trace"
+    ));
+}
+
+#[test]
+fn finds_private_addresses_including_mapped_and_bracketed() {
+    assert!(private_ips::find_private_ips(&common::load("multi_page.har")).is_empty());
+
+    let results: Vec<(usize, IpRange)> = private_ips::find_private_ips(&common::load("quirks.har"))
+        .into_iter()
+        .map(|result| (result.request_num, result.range))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            (1, IpRange::Private),
+            (2, IpRange::Private),
+            (3, IpRange::UniqueLocal),
+        ]
+    );
+}
+
+#[test]
+fn classifies_ipv6_ranges() {
+    let classify = |ip: &str| private_ips::classify(ip.parse::<IpAddr>().unwrap());
+
+    assert_eq!(classify("::ffff:127.0.0.1"), Some(IpRange::Loopback));
+    assert_eq!(classify("::ffff:8.8.8.8"), None);
+    assert_eq!(classify("fc00::1"), Some(IpRange::UniqueLocal));
+    assert_eq!(classify("fdff:ffff::1"), Some(IpRange::UniqueLocal));
+    assert_eq!(classify("fe00::1"), None);
+    assert_eq!(classify("fe80::1"), Some(IpRange::LinkLocal));
+    assert_eq!(classify("::1"), Some(IpRange::Loopback));
+    assert_eq!(classify("2001:db8::1"), None);
+}
+
+#[test]
+fn duplicate_headers_need_conflicting_values() {
+    assert!(duplicate_headers::duplicate_headers(&common::load("multi_page.har")).is_empty());
+
+    let duplicates = duplicate_headers::duplicate_headers(&common::load("quirks.har"));
+    assert_eq!(duplicates.len(), 1);
+    let duplicate = &duplicates[0];
+    assert_eq!((duplicate.request_num, duplicate.side), (2, "request"));
+    assert_eq!(duplicate.name, "Cache-Control");
+    assert_eq!(duplicate.values, ["no-cache", "max-age=0"]);
+
+    // the same value twice isn't a conflict
+    let mut har = common::load("quirks.har");
+    har.log.entries[1].request.headers[3].value = "no-cache".to_string();
+    assert!(duplicate_headers::duplicate_headers(&har).is_empty());
+}
+
+#[test]
+fn finds_streamed_responses() {
+    let results: Vec<(usize, StreamingReason)> = chunked::find_chunked(&common::load("quirks.har"))
+        .into_iter()
+        .map(|result| (result.request_num, result.reason))
+        .collect();
+
+    // the 204 preflight has no body to measure
+    assert_eq!(
+        results,
+        [
+            (2, StreamingReason::Chunked),
+            (3, StreamingReason::NoContentLength),
+        ]
+    );
+}
+
+#[test]
+fn mime_mismatch_checks_the_extension() {
+    // the 301 for /checkout has no extension and is a redirect anyway
+    assert!(mime_mismatch::find_mismatches(&common::load("multi_page.har")).is_empty());
+
+    let mismatches = mime_mismatch::find_mismatches(&common::load("quirks.har"));
+    assert_eq!(mismatches.len(), 1);
+    let mismatch = &mismatches[0];
+    assert_eq!(mismatch.request_num, 3);
+    assert_eq!(mismatch.expected, "image/png");
+    assert_eq!(mismatch.actual, "text/html");
+}
+
+#[test]
+fn collects_comments_with_their_location() {
+    assert!(comments::comments(&common::load("minimal.har")).is_empty());
+
+    let found: Vec<(String, Option<usize>, String)> =
+        comments::comments(&common::load("quirks.har"))
+            .into_iter()
+            .map(|comment| (comment.location, comment.request_num, comment.text))
+            .collect();
+    assert_eq!(
+        found,
+        [
+            (
+                "log".to_string(),
+                None,
+                "Edge cases for the audits".to_string()
+            ),
+            ("page page_1".to_string(), None, "Items page".to_string()),
+            ("entry".to_string(), Some(2), "Retried once".to_string()),
+        ]
+    );
+}
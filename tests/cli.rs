@@ -0,0 +1,150 @@
+use std::{fs, path::PathBuf, process::Command};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// Run harper on a fixture, returning its stdout.
+fn harper(file: &str, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_harper"))
+        .arg(fixture(file))
+        .arg("--no-pager")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn output_of_a_directory_is_the_merged_document() {
+    let dir = std::env::temp_dir().join(format!("harper-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for name in ["initiators.har", "multi_page.har"] {
+        fs::copy(fixture(name), dir.join(name)).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_harper"))
+        .arg(&dir)
+        .args(["--no-pager", "output"])
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let har: harper::Har = serde_json::from_str(&stdout).unwrap();
+    let doc = json::parse(&stdout).unwrap();
+
+    let expected = ["initiators.har", "multi_page.har"]
+        .map(|name| {
+            let contents = fs::read_to_string(fixture(name)).unwrap();
+            json::parse(&contents).unwrap()["log"]["entries"].len()
+        })
+        .iter()
+        .sum::<usize>();
+    assert_eq!(har.log.entries.len(), expected);
+
+    // fields the model doesn't know survive and none are made up as null
+    assert!(doc["log"]["entries"]
+        .members()
+        .any(|entry| entry["_initiator"].is_object()));
+    assert!(!stdout.contains("null"));
+}
+
+#[test]
+fn count_urls_flat_prints_leaves_as_hosts() {
+    assert_eq!(
+        harper(
+            "multi_page.har",
+            &["count-urls", "--flat", "--sort", "alpha"]
+        ),
+        "api.example.com: 2\nshop.example.com: 3\ncdn.example.net: 3\ntracker.example.org: 1\n"
+    );
+    assert_eq!(
+        harper("multi_page.har", &["count-urls", "--total-only"]),
+        "9\n"
+    );
+    assert_eq!(
+        harper("multi_page.har", &["--json", "count-urls", "--total-only"]).trim(),
+        "9"
+    );
+}
+
+#[test]
+fn entry_numbers_count_the_filtered_entries() {
+    let url = |output: String| json::parse(&output).unwrap()["request"]["url"].to_string();
+
+    assert_eq!(
+        url(harper("multi_page.har", &["--range", "1:", "entry", "1"])),
+        "https://cdn.example.net/static/app.js"
+    );
+    assert_eq!(
+        url(harper(
+            "multi_page.har",
+            &["--has-header", "Authorization", "entry", "2"]
+        )),
+        "https://api.example.com/v1/orders"
+    );
+    assert_eq!(
+        url(harper("multi_page.har", &["--range", ":3", "entry", "-1"])),
+        "https://cdn.example.net/static/style.css"
+    );
+
+    // search-for numbers requests the same way
+    let found = harper(
+        "multi_page.har",
+        &["--json", "--range", "1:", "search-for", "Widget"],
+    );
+    assert_eq!(json::parse(&found).unwrap()["matches"][0]["request_num"], 4);
+    assert_eq!(
+        url(harper("multi_page.har", &["--range", "1:", "entry", "4"])),
+        "https://api.example.com/v1/products?page=1&limit=20"
+    );
+}
+
+#[test]
+fn redact_only_outputs_the_filtered_entries() {
+    let har = json::parse(&harper("multi_page.har", &["--range", "1:3", "redact"])).unwrap();
+
+    let urls: Vec<String> = har["log"]["entries"]
+        .members()
+        .map(|entry| entry["request"]["url"].to_string())
+        .collect();
+    assert_eq!(
+        urls,
+        [
+            "https://cdn.example.net/static/app.js",
+            "https://cdn.example.net/static/style.css",
+        ]
+    );
+    assert_eq!(har["log"]["pages"].len(), 2);
+}
+
+#[test]
+fn annotate_takes_the_site_from_the_document_not_the_filtered_entries() {
+    // the filter drops shop.example.com's first request, output doesn't
+    let har = json::parse(&harper(
+        "multi_page.har",
+        &["--range", "1:", "output", "--annotate"],
+    ))
+    .unwrap();
+
+    let parties: Vec<String> = har["log"]["entries"]
+        .members()
+        .map(|entry| entry["_harper"]["party"].to_string())
+        .collect();
+    assert_eq!(parties[0], "first");
+    assert_eq!(parties[1], "third");
+}
@@ -0,0 +1,40 @@
+// each test crate only uses some of the helpers
+#![allow(dead_code)]
+
+use harper::Har;
+use std::{fs, path::PathBuf};
+
+/// Parse a HAR from `tests/fixtures`.
+pub fn load(name: &str) -> Har {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+
+    serde_json::from_str(&contents)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e))
+}
+
+/// Parse a HAR from `tests/fixtures` as a raw document, for the commands that
+/// rewrite it.
+pub fn load_raw(name: &str) -> json::JsonValue {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+
+    json::parse(&contents).unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e))
+}
+
+/// Request URLs of the entries left in a HAR.
+pub fn urls(har: &Har) -> Vec<&str> {
+    har.log
+        .entries
+        .iter()
+        .map(|entry| entry.request.url.as_str())
+        .collect()
+}
@@ -0,0 +1,277 @@
+mod common;
+
+use harper::{
+    compression, connections, cookie_flow, count_requests, count_schemes, count_urls, counts,
+    host_mimes, list_domains, ports, user_agents,
+};
+use tldextract::TldOption;
+
+#[test]
+fn counts_requests() {
+    assert_eq!(
+        count_requests::get_counts(&common::load("multi_page.har")),
+        9
+    );
+    assert_eq!(count_requests::get_counts(&common::load("minimal.har")), 1);
+}
+
+#[test]
+fn counts_schemes() {
+    let counts = count_schemes::get_counts(&common::load("multi_page.har"));
+
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts["https"], 8);
+    assert_eq!(counts["http"], 1);
+}
+
+#[test]
+fn frequency_ties_break_by_key() {
+    let counts = count_schemes::get_counts(&common::load("multi_page.har"));
+    let ordered: Vec<(&str, usize)> = counts::by_frequency(&counts)
+        .into_iter()
+        .map(|(scheme, count)| (scheme.as_str(), count))
+        .collect();
+
+    assert_eq!(ordered, [("https", 8), ("http", 1)]);
+}
+
+#[test]
+fn lists_unique_domains() {
+    let mut domains = list_domains::list_domains(&common::load("multi_page.har"));
+    domains.sort();
+
+    assert_eq!(
+        domains,
+        [
+            "api.example.com",
+            "cdn.example.net",
+            "shop.example.com",
+            "tracker.example.org",
+        ]
+    );
+}
+
+#[test]
+fn counts_requests_per_domain_sorted_by_reversed_name() {
+    let har = common::load("multi_page.har");
+    let counts: Vec<(String, usize)> = list_domains::domain_counts(&har)
+        .into_iter()
+        .map(|count| (count.domain, count.requests))
+        .collect();
+
+    assert_eq!(
+        counts,
+        [
+            ("tracker.example.org".to_string(), 1),
+            ("api.example.com".to_string(), 2),
+            ("shop.example.com".to_string(), 3),
+            ("cdn.example.net".to_string(), 3),
+        ]
+    );
+    let domains: Vec<String> = counts.into_iter().map(|(domain, _)| domain).collect();
+    assert_eq!(list_domains::sorted_domains(&har), domains);
+}
+
+#[test]
+fn groups_mime_types_by_host() {
+    let results = host_mimes::host_mimes(&common::load("multi_page.har"));
+
+    let hosts: Vec<(&str, usize)> = results
+        .iter()
+        .map(|result| (result.host.as_str(), result.requests))
+        .collect();
+    assert_eq!(
+        hosts,
+        [
+            ("cdn.example.net", 3),
+            ("shop.example.com", 3),
+            ("api.example.com", 2),
+            ("tracker.example.org", 1),
+        ]
+    );
+
+    let shop = &results[1];
+    assert_eq!(shop.mime_types.len(), 1);
+    assert_eq!(shop.mime_types[0].mime_type, "text/html");
+    assert_eq!(shop.mime_types[0].count, 3);
+}
+
+#[test]
+fn standard_ports_are_not_reported() {
+    assert!(ports::non_standard_ports(&common::load("multi_page.har")).is_empty());
+}
+
+#[test]
+fn counts_user_agents() {
+    let counts = user_agents::user_agents(&common::load("multi_page.har"));
+
+    assert_eq!(counts.len(), 1);
+    assert_eq!(
+        counts["Mozilla/5.0 (X11; Linux x86_64; rv:124.0) Gecko/20100101 Firefox/124.0"],
+        9
+    );
+
+    let counts = user_agents::user_agents(&common::load("minimal.har"));
+    assert_eq!(counts[""], 1);
+}
+
+#[test]
+fn count_json_is_in_key_order() {
+    let har = common::load("multi_page.har");
+
+    let json = serde_json::to_string(&count_schemes::get_counts(&har)).unwrap();
+    assert_eq!(json, r#"{"http":1,"https":8}"#);
+
+    let keys = |value: serde_json::Value| -> Vec<String> {
+        value.as_object().unwrap().keys().cloned().collect()
+    };
+    let sorted = |mut keys: Vec<String>| {
+        keys.sort();
+        keys
+    };
+
+    let flows = serde_json::to_value(cookie_flow::cookie_flow(&har)).unwrap();
+    assert_eq!(keys(flows.clone()), sorted(keys(flows)));
+
+    let mimes = serde_json::to_value(compression::compression_by_mime(&har)).unwrap();
+    assert_eq!(keys(mimes.clone()), sorted(keys(mimes)));
+
+    let stats = serde_json::to_value(connections::get_connection_stats(&har)).unwrap();
+    let connections = stats["connections"].clone();
+    assert_eq!(keys(connections.clone()), sorted(keys(connections)));
+
+    let extractor = TldOption::default().build();
+    let mut tree = count_urls::DomainNode::default();
+    count_urls::build_domain_tree(
+        &har,
+        &mut tree,
+        &extractor,
+        count_urls::TreeOptions::default(),
+    );
+    let top = serde_json::to_value(&tree).unwrap()["children"].clone();
+    assert_eq!(keys(top.clone()), sorted(keys(top)));
+}
+
+fn children(node: &count_urls::DomainNode) -> Vec<(&str, usize)> {
+    node.children
+        .iter()
+        .map(|(name, child)| (name.as_str(), child.count))
+        .collect()
+}
+
+#[test]
+fn builds_domain_tree_from_suffix_down() {
+    let har = common::load("multi_page.har");
+    let extractor = TldOption::default().build();
+    let mut tree = count_urls::DomainNode::default();
+    count_urls::build_domain_tree(
+        &har,
+        &mut tree,
+        &extractor,
+        count_urls::TreeOptions::default(),
+    );
+
+    assert_eq!(children(&tree), [("com", 5), ("net", 3), ("org", 1)]);
+    let example = &tree.children["com"].children["example"];
+    assert_eq!(children(example), [("api", 2), ("shop", 3)]);
+    assert_eq!(tree.total(), 9);
+    assert_eq!(tree.depth(), 3);
+
+    let mut merged = count_urls::DomainNode::default();
+    count_urls::build_domain_tree(
+        &har,
+        &mut merged,
+        &extractor,
+        count_urls::TreeOptions {
+            merge_tld: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        children(&merged),
+        [("example.com", 5), ("example.net", 3), ("example.org", 1)]
+    );
+}
+
+#[test]
+fn merge_www_counts_www_as_the_bare_domain() {
+    let mut har = common::load("multi_page.har");
+    har.log.entries.truncate(2);
+    har.log.entries[0].request.url = "https://www.example.com/".to_string();
+    har.log.entries[1].request.url = "https://example.com/about".to_string();
+
+    let extractor = TldOption::default().build();
+    let tree_with = |merge_www| {
+        let mut tree = count_urls::DomainNode::default();
+        count_urls::build_domain_tree(
+            &har,
+            &mut tree,
+            &extractor,
+            count_urls::TreeOptions {
+                merge_www,
+                ..Default::default()
+            },
+        );
+        tree
+    };
+
+    let tree = tree_with(false);
+    assert_eq!(
+        children(&tree.children["com"].children["example"]),
+        [("", 1), ("www", 1)]
+    );
+    let tree = tree_with(true);
+    assert_eq!(
+        children(&tree.children["com"].children["example"]),
+        [("", 2)]
+    );
+    assert_eq!(tree.total(), 2);
+}
+
+#[test]
+fn data_uris_are_only_counted_when_included() {
+    let mut har = common::load("minimal.har");
+    har.log.entries[0].request.url = "data:image/png;base64,iVBORw0KGgo=".to_string();
+    let extractor = TldOption::default().build();
+
+    let mut tree = count_urls::DomainNode::default();
+    count_urls::build_domain_tree(
+        &har,
+        &mut tree,
+        &extractor,
+        count_urls::TreeOptions::default(),
+    );
+    assert_eq!(tree.total(), 0);
+
+    count_urls::build_domain_tree(
+        &har,
+        &mut tree,
+        &extractor,
+        count_urls::TreeOptions {
+            include_data_uris: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(children(&tree), [(count_urls::DATA_URI_NODE, 1)]);
+}
+
+#[test]
+fn cookie_flow_tracks_cookies_sent_after_being_set() {
+    let flows = cookie_flow::cookie_flow(&common::load("multi_page.har"));
+
+    assert_eq!(flows.len(), 1);
+    let session = &flows["session"];
+    assert_eq!(
+        (session.set, session.sent, session.sent_after_set),
+        (1, 1, 1)
+    );
+    assert!(!session.never_used() && !session.never_set());
+
+    let flows = cookie_flow::cookie_flow(&common::load("quirks.har"));
+    let names: Vec<&str> = flows.keys().map(String::as_str).collect();
+    assert_eq!(names, ["prefs", "session", "theme"]);
+    // sent before the response that set it
+    assert!(flows["session"].never_used());
+    assert!(flows["prefs"].never_used());
+    assert!(flows["theme"].never_set());
+}
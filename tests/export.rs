@@ -0,0 +1,199 @@
+mod common;
+
+use harper::{export_postman, redact};
+
+fn header<'a>(headers: &'a json::JsonValue, name: &str) -> &'a str {
+    headers
+        .members()
+        .find(|header| header["name"] == name)
+        .and_then(|header| header["value"].as_str())
+        .unwrap_or_else(|| panic!("no {} header", name))
+}
+
+#[test]
+fn redacts_credentials_keeping_names() {
+    let mut har = common::load_raw("multi_page.har");
+    redact::redact(&mut har, false);
+
+    let entries = &har["log"]["entries"];
+    assert_eq!(
+        header(&entries[0]["response"]["headers"], "Set-Cookie"),
+        "session=[REDACTED]; Path=/; HttpOnly; Secure"
+    );
+    assert_eq!(
+        entries[0]["response"]["cookies"][0]["value"],
+        redact::REDACTED
+    );
+    assert_eq!(
+        header(&entries[6]["request"]["headers"], "Cookie"),
+        "session=[REDACTED]"
+    );
+    assert_eq!(
+        header(&entries[4]["request"]["headers"], "Authorization"),
+        redact::REDACTED
+    );
+    // not a credential
+    assert_eq!(
+        header(&entries[4]["request"]["headers"], "Origin"),
+        "https://shop.example.com"
+    );
+
+    assert_eq!(entries[7]["request"]["postData"]["text"], redact::REDACTED);
+    assert_eq!(entries[4]["response"]["content"]["text"], redact::REDACTED);
+    assert!(!entries[3]["response"]["content"].has_key("encoding"));
+}
+
+#[test]
+fn redacts_every_cookie_and_set_cookie_line() {
+    let mut har = common::load_raw("quirks.har");
+    redact::redact(&mut har, true);
+
+    let entry = &har["log"]["entries"][1];
+    assert_eq!(
+        header(&entry["request"]["headers"], "Cookie"),
+        "session=[REDACTED]; theme=[REDACTED]"
+    );
+    assert_eq!(
+        header(&entry["response"]["headers"], "Set-Cookie"),
+        "session=[REDACTED]; Path=/; HttpOnly\nprefs=[REDACTED]"
+    );
+}
+
+#[test]
+fn redacts_secret_query_parameters_in_the_url() {
+    let mut har = common::load_raw("quirks.har");
+    redact::redact(&mut har, true);
+
+    let request = &har["log"]["entries"][1]["request"];
+    assert_eq!(
+        request["url"],
+        "https://api.example.com/v1/items?token=%5BREDACTED%5D&page=2"
+    );
+    assert_eq!(request["queryString"][0]["value"], redact::REDACTED);
+    assert_eq!(request["queryString"][1]["value"], "2");
+
+    // only the URL and query string are rewritten when bodies are kept
+    let post_data = &har["log"]["entries"][2]["request"]["postData"];
+    assert_eq!(post_data["text"], "q=widget&password=hunter2");
+
+    // URLs without secrets are left as written
+    let mut har = common::load_raw("multi_page.har");
+    redact::redact(&mut har, true);
+    assert_eq!(
+        har["log"]["entries"][4]["request"]["url"],
+        "https://api.example.com/v1/products?page=1&limit=20"
+    );
+}
+
+#[test]
+fn exports_postman_folders_by_host() {
+    let collection = export_postman::export_postman(&common::load("multi_page.har"));
+
+    assert_eq!(collection.info.name, "Shop - Home");
+    let folders: Vec<(&str, usize)> = collection
+        .item
+        .iter()
+        .map(|folder| (folder.name.as_str(), folder.item.len()))
+        .collect();
+    assert_eq!(
+        folders,
+        [
+            ("shop.example.com", 3),
+            ("cdn.example.net", 3),
+            ("api.example.com", 2),
+            ("tracker.example.org", 1),
+        ]
+    );
+
+    let products = &collection.item[2].item[0];
+    assert_eq!(products.name, "GET /v1/products");
+    let url = &products.request.url;
+    assert_eq!(url.protocol.as_deref(), Some("https"));
+    assert_eq!(url.host, ["api", "example", "com"]);
+    assert_eq!(url.path, ["v1", "products"]);
+    let query: Vec<(&str, &str)> = url
+        .query
+        .iter()
+        .map(|kv| (kv.key.as_str(), kv.value.as_str()))
+        .collect();
+    assert_eq!(query, [("page", "1"), ("limit", "20")]);
+
+    let order = &collection.item[2].item[1].request;
+    let body = order.body.as_ref().unwrap();
+    assert_eq!(body.mode, "raw");
+    assert!(body.raw.as_deref().unwrap().contains("SPRING24"));
+}
+
+#[test]
+fn exports_form_bodies_as_urlencoded_without_pseudo_headers() {
+    let collection = export_postman::export_postman(&common::load("quirks.har"));
+
+    assert_eq!(collection.info.name, "Quirks");
+    let request = &collection.item[1].item[0].request;
+    let headers: Vec<&str> = request.header.iter().map(|kv| kv.key.as_str()).collect();
+    assert_eq!(headers, ["Content-Type"]);
+
+    let body = request.body.as_ref().unwrap();
+    assert_eq!(body.mode, "urlencoded");
+    assert!(body.raw.is_none());
+    let params: Vec<(&str, &str)> = body
+        .urlencoded
+        .iter()
+        .flatten()
+        .map(|kv| (kv.key.as_str(), kv.value.as_str()))
+        .collect();
+    assert_eq!(params, [("q", "widget"), ("password", "hunter2")]);
+}
+
+fn redacted_leak() -> json::JsonValue {
+    let mut har = common::load_raw("leak.har");
+    redact::redact(&mut har, true);
+    har["log"]["entries"][0].take()
+}
+
+#[test]
+fn redacts_query_secrets_in_the_path_pseudo_header() {
+    let entry = redacted_leak();
+    assert_eq!(
+        header(&entry["request"]["headers"], ":path"),
+        "/callback?token=%5BREDACTED%5D&state=xyz"
+    );
+}
+
+#[test]
+fn redacts_query_secrets_in_the_referer() {
+    let entry = redacted_leak();
+    // other parameters keep their encoding
+    assert_eq!(
+        header(&entry["request"]["headers"], "Referer"),
+        "https://app.example.com/start?session=%5BREDACTED%5D&lang=en%20GB"
+    );
+}
+
+#[test]
+fn redacts_query_secrets_in_a_relative_location() {
+    let entry = redacted_leak();
+    assert_eq!(
+        header(&entry["response"]["headers"], "Location"),
+        "/home?auth=%5BREDACTED%5D#top"
+    );
+}
+
+#[test]
+fn redacts_query_secrets_in_the_content_location() {
+    let entry = redacted_leak();
+    assert_eq!(
+        header(&entry["response"]["headers"], "Content-Location"),
+        "https://app.example.com/done?api_key=%5BREDACTED%5D"
+    );
+}
+
+#[test]
+fn redacts_query_secrets_in_the_redirect_url() {
+    let entry = redacted_leak();
+    assert_eq!(
+        entry["response"]["redirectURL"],
+        "/home?auth=%5BREDACTED%5D#top"
+    );
+    assert!(!entry.dump().contains("SECRET"));
+}
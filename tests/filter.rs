@@ -0,0 +1,314 @@
+mod common;
+
+use chrono::DateTime;
+use harper::{
+    body::BodySide,
+    filter::{self, HeaderMatch, IndexRange, PhaseThreshold, SizeRange, TimeArg, TimeBound},
+};
+
+#[test]
+fn after_keeps_later_entries() {
+    let mut har = common::load("multi_page.har");
+    let time = DateTime::parse_from_rfc3339("2024-03-12T09:15:05Z").unwrap();
+
+    filter::filter_by_time(&mut har, time, TimeBound::After);
+
+    assert_eq!(har.log.entries.len(), 4);
+    assert!(har
+        .log
+        .entries
+        .iter()
+        .all(|entry| entry.pageref.as_deref() == Some("page_2")));
+}
+
+#[test]
+fn before_keeps_earlier_entries() {
+    let mut har = common::load("multi_page.har");
+    let time = DateTime::parse_from_rfc3339("2024-03-12T09:15:05Z").unwrap();
+
+    filter::filter_by_time(&mut har, time, TimeBound::Before);
+
+    assert_eq!(har.log.entries.len(), 5);
+}
+
+/// Request 7 starts at 2024-03-12T09:15:10.050Z.
+fn filter_at(time: &str, bound: TimeBound) -> Vec<String> {
+    let mut har = common::load("multi_page.har");
+    let time = DateTime::parse_from_rfc3339(time).unwrap();
+
+    filter::filter_by_time(&mut har, time, bound);
+
+    common::urls(&har).into_iter().map(String::from).collect()
+}
+
+const CHECKOUT: &str = "https://shop.example.com/checkout";
+
+#[test]
+fn after_includes_entry_at_boundary() {
+    let urls = filter_at("2024-03-12T09:15:10.050Z", TimeBound::After);
+
+    assert_eq!(urls.len(), 3);
+    assert_eq!(urls[0], CHECKOUT);
+}
+
+#[test]
+fn after_excludes_entry_a_millisecond_before() {
+    let urls = filter_at("2024-03-12T09:15:10.051Z", TimeBound::After);
+
+    assert_eq!(urls.len(), 2);
+    assert!(!urls.contains(&CHECKOUT.to_string()));
+}
+
+#[test]
+fn before_includes_entry_at_boundary() {
+    let urls = filter_at("2024-03-12T09:15:10.050Z", TimeBound::Before);
+
+    assert_eq!(urls.len(), 7);
+    assert_eq!(urls[6], CHECKOUT);
+}
+
+#[test]
+fn before_excludes_entry_a_millisecond_after() {
+    let urls = filter_at("2024-03-12T09:15:10.049Z", TimeBound::Before);
+
+    assert_eq!(urls.len(), 6);
+    assert!(!urls.contains(&CHECKOUT.to_string()));
+}
+
+#[test]
+fn boundary_compares_instants_across_offsets() {
+    let urls = filter_at("2024-03-12T10:15:10.050+01:00", TimeBound::After);
+
+    assert_eq!(urls[0], CHECKOUT);
+}
+
+#[test]
+fn same_bound_on_both_sides_keeps_entries_at_time() {
+    let mut har = common::load("multi_page.har");
+    let time = DateTime::parse_from_rfc3339("2024-03-12T09:15:10.050Z").unwrap();
+
+    filter::filter_by_time(&mut har, time, TimeBound::After);
+    filter::filter_by_time(&mut har, time, TimeBound::Before);
+
+    assert_eq!(common::urls(&har), [CHECKOUT]);
+}
+
+#[test]
+fn times_without_offset_resolve_in_timezone() {
+    let time: TimeArg = "2024-03-12T10:15:05".parse().unwrap();
+    let resolved = time.resolve(Some(chrono_tz::Europe::Paris)).unwrap();
+
+    assert_eq!(
+        resolved,
+        DateTime::parse_from_rfc3339("2024-03-12T09:15:05Z").unwrap()
+    );
+}
+
+#[test]
+fn index_range_is_half_open() {
+    let mut har = common::load("multi_page.har");
+    let range: IndexRange = "1:3".parse().unwrap();
+
+    filter::filter_by_index(&mut har, range);
+
+    assert_eq!(
+        common::urls(&har),
+        [
+            "https://cdn.example.net/static/app.js",
+            "https://cdn.example.net/static/style.css",
+        ]
+    );
+}
+
+#[test]
+fn request_filters_use_printed_numbers() {
+    let original = common::load("multi_page.har");
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_after_request(&mut har, 7);
+    assert_eq!(request_nums(&har, &original), [8, 9]);
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_before_request(&mut har, 3);
+    assert_eq!(request_nums(&har, &original), [1, 2]);
+
+    // before the end is cut, then after, keeps the requests in between
+    let mut har = common::load("multi_page.har");
+    filter::filter_before_request(&mut har, 6);
+    filter::filter_after_request(&mut har, 3);
+    assert_eq!(request_nums(&har, &original), [4, 5]);
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_after_request(&mut har, 9);
+    assert!(har.log.entries.is_empty());
+}
+
+#[test]
+fn index_range_rejects_reversed_bounds() {
+    assert!("5:2".parse::<IndexRange>().is_err());
+}
+
+#[test]
+fn around_match_keeps_window() {
+    let mut har = common::load("multi_page.har");
+
+    filter::filter_around_match(&mut har, "ord_789", 1);
+
+    assert_eq!(
+        common::urls(&har),
+        [
+            "https://shop.example.com/checkout",
+            "https://api.example.com/v1/orders",
+            "https://tracker.example.org/pixel.gif?uid=42&event=checkout",
+        ]
+    );
+}
+
+#[test]
+fn around_match_without_match_keeps_nothing() {
+    let mut har = common::load("multi_page.har");
+
+    filter::filter_around_match(&mut har, "not in the capture", 5);
+
+    assert!(har.log.entries.is_empty());
+}
+
+#[test]
+fn slow_phase_keeps_entries_over_threshold() {
+    let mut har = common::load("multi_page.har");
+    let threshold: PhaseThreshold = "wait:100".parse().unwrap();
+
+    filter::filter_by_phase(&mut har, threshold);
+
+    assert_eq!(common::urls(&har), ["https://api.example.com/v1/orders"]);
+}
+
+fn body_sizes(har: &harper::Har) -> Vec<i64> {
+    har.log
+        .entries
+        .iter()
+        .map(|entry| entry.response.body_size)
+        .collect()
+}
+
+#[test]
+fn size_range_is_inclusive() {
+    let mut har = common::load("multi_page.har");
+
+    filter::filter_by_size(&mut har, "18:45".parse().unwrap());
+
+    assert_eq!(body_sizes(&har), [18, 19, 39, 45]);
+}
+
+#[test]
+fn size_range_can_be_open_ended() {
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_size(&mut har, "500:".parse().unwrap());
+    assert_eq!(body_sizes(&har), [512, 640]);
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_size(&mut har, ":16".parse().unwrap());
+    assert_eq!(body_sizes(&har), [16, 0, 14]);
+}
+
+#[test]
+fn unknown_sizes_are_dropped_only_with_a_minimum() {
+    let mut har = common::load("multi_page.har");
+    har.log.entries[0].response.body_size = -1;
+    filter::filter_by_size(&mut har, ":20".parse().unwrap());
+    assert_eq!(body_sizes(&har), [-1, 18, 19, 16, 0, 14]);
+
+    let mut har = common::load("multi_page.har");
+    har.log.entries[0].response.body_size = -1;
+    filter::filter_by_size(&mut har, "0:".parse().unwrap());
+    assert_eq!(har.log.entries.len(), 8);
+}
+
+#[test]
+fn size_range_rejects_invalid_bounds() {
+    assert!("10:5".parse::<SizeRange>().is_err());
+    assert!("10KB:".parse::<SizeRange>().is_err());
+    assert!("100".parse::<SizeRange>().is_err());
+}
+
+/// Request numbers in the original capture of the entries left, the fixture's
+/// URLs are all different.
+fn request_nums(har: &harper::Har, original: &harper::Har) -> Vec<usize> {
+    let original_urls = common::urls(original);
+    common::urls(har)
+        .iter()
+        .map(|url| original_urls.iter().position(|u| u == url).unwrap() + 1)
+        .collect()
+}
+
+#[test]
+fn has_header_matches_name_case_insensitively_on_either_side() {
+    let original = common::load("multi_page.har");
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_header(&mut har, &"authorization".parse().unwrap());
+    assert_eq!(request_nums(&har, &original), [5, 8]);
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_header(&mut har, &"CACHE-CONTROL".parse().unwrap());
+    assert_eq!(request_nums(&har, &original), [1, 2, 4]);
+}
+
+#[test]
+fn has_header_value_must_match() {
+    let original = common::load("multi_page.har");
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_header(&mut har, &"Content-Type: application/json".parse().unwrap());
+    assert_eq!(request_nums(&har, &original), [5, 8]);
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_header(&mut har, &"Content-Type:application".parse().unwrap());
+    assert!(har.log.entries.is_empty());
+}
+
+#[test]
+fn has_header_filters_combine() {
+    let original = common::load("multi_page.har");
+    let mut har = common::load("multi_page.har");
+
+    for header in ["Authorization", "Content-Type:application/json", "Origin"] {
+        filter::filter_by_header(&mut har, &header.parse().unwrap());
+    }
+    assert_eq!(request_nums(&har, &original), [5, 8]);
+
+    filter::filter_by_header(&mut har, &"Cookie".parse().unwrap());
+    assert!(har.log.entries.is_empty());
+}
+
+#[test]
+fn header_match_needs_a_name() {
+    let header: HeaderMatch = " X-Debug : 1 ".parse().unwrap();
+    assert_eq!(header.name, "X-Debug");
+    assert_eq!(header.value.as_deref(), Some("1"));
+
+    assert!(":value".parse::<HeaderMatch>().is_err());
+    assert!("".parse::<HeaderMatch>().is_err());
+}
+
+#[test]
+fn body_presence_drops_empty_bodies() {
+    let original = common::load("multi_page.har");
+
+    // the redirect has no content
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_body_presence(&mut har, BodySide::Response);
+    assert_eq!(request_nums(&har, &original), [1, 2, 3, 4, 5, 7, 8, 9]);
+
+    let mut har = common::load("multi_page.har");
+    filter::filter_by_body_presence(&mut har, BodySide::Request);
+    assert_eq!(request_nums(&har, &original), [8]);
+
+    // a 204 recording a size of 0 and no text
+    let mut har = common::load("multi_page.har");
+    let content = har.log.entries[8].response.content.as_mut().unwrap();
+    content.size = Some(0);
+    content.text = None;
+    filter::filter_by_body_presence(&mut har, BodySide::Response);
+    assert_eq!(request_nums(&har, &original), [1, 2, 3, 4, 5, 7, 8]);
+}
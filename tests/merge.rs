@@ -0,0 +1,95 @@
+mod common;
+
+use harper::merge;
+
+#[test]
+fn merges_entries_in_order() {
+    let merged = merge::merge([
+        ("multi_page.har".to_string(), common::load("multi_page.har")),
+        ("retries.har".to_string(), common::load("retries.har")),
+    ])
+    .unwrap();
+
+    let mut expected = common::urls(&common::load("multi_page.har"))
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    expected.extend(
+        common::urls(&common::load("retries.har"))
+            .into_iter()
+            .map(String::from),
+    );
+    assert_eq!(common::urls(&merged), expected);
+    assert_eq!(merged.log.creator.name, "Firefox");
+}
+
+#[test]
+fn renames_clashing_page_ids() {
+    let merged = merge::merge([
+        ("a.har".to_string(), common::load("multi_page.har")),
+        ("b.har".to_string(), common::load("multi_page.har")),
+    ])
+    .unwrap();
+
+    let ids: Vec<&str> = merged
+        .log
+        .pages
+        .iter()
+        .flatten()
+        .map(|page| page.id.as_str())
+        .collect();
+    assert_eq!(ids, ["page_1", "page_2", "b.har:page_1", "b.har:page_2"]);
+
+    let entries = &merged.log.entries;
+    assert_eq!(entries.len(), 18);
+    assert_eq!(entries[0].pageref.as_deref(), Some("page_1"));
+    assert_eq!(entries[9].pageref.as_deref(), Some("b.har:page_1"));
+    assert_eq!(entries[17].pageref.as_deref(), Some("b.har:page_2"));
+}
+
+#[test]
+fn nothing_to_merge() {
+    assert!(merge::merge(Vec::new()).is_none());
+}
+
+#[test]
+fn raw_merge_renames_like_merge() {
+    let merged = merge::merge_raw([
+        ("a.har".to_string(), common::load_raw("multi_page.har")),
+        ("b.har".to_string(), common::load_raw("multi_page.har")),
+    ])
+    .unwrap();
+
+    let ids: Vec<&str> = merged["log"]["pages"]
+        .members()
+        .filter_map(|page| page["id"].as_str())
+        .collect();
+    assert_eq!(ids, ["page_1", "page_2", "b.har:page_1", "b.har:page_2"]);
+
+    let entries = &merged["log"]["entries"];
+    assert_eq!(entries.len(), 18);
+    assert_eq!(entries[0]["pageref"], "page_1");
+    assert_eq!(entries[9]["pageref"], "b.har:page_1");
+    assert_eq!(entries[17]["pageref"], "b.har:page_2");
+}
+
+#[test]
+fn raw_merge_keeps_unknown_fields() {
+    let merged = merge::merge_raw([
+        ("a.har".to_string(), common::load_raw("multi_page.har")),
+        ("b.har".to_string(), common::load_raw("initiators.har")),
+    ])
+    .unwrap();
+
+    let initiators = |har: &json::JsonValue| {
+        har["log"]["entries"]
+            .members()
+            .filter(|entry| !entry["_initiator"].is_null())
+            .count()
+    };
+    assert_eq!(
+        initiators(&merged),
+        initiators(&common::load_raw("initiators.har"))
+    );
+    assert!(!merged.dump().contains("null"));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use common::load_raw;
+use harper::pages;
+
+fn urls(har: &json::JsonValue) -> Vec<&str> {
+    har["log"]["entries"]
+        .members()
+        .map(|entry| entry["request"]["url"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn groups_interleaved_pages_keeping_every_entry() {
+    let mut har = load_raw("multi_page.har");
+    let original = har.clone();
+    let original_urls = urls(&original);
+
+    // interleave the pages, and take the second entry out of any page
+    let mut entries: Vec<json::JsonValue> = har["log"]["entries"]
+        .members_mut()
+        .map(json::JsonValue::take)
+        .collect();
+    let checkout = entries.remove(5);
+    entries.insert(0, checkout);
+    let checkout = entries.remove(6);
+    entries.insert(3, checkout);
+    entries[2].remove("pageref");
+    har["log"]["entries"] = entries.into();
+
+    let groups = pages::group_by_page(&mut har);
+
+    let summary: Vec<(Option<&str>, usize)> = groups
+        .iter()
+        .map(|group| (group.pageref.as_deref(), group.entries))
+        .collect();
+    assert_eq!(
+        summary,
+        [(Some("page_1"), 4), (Some("page_2"), 4), (None, 1)]
+    );
+    assert_eq!(groups[0].title.as_deref(), Some("Shop - Home"));
+
+    // each page keeps its entries in their original order
+    let expected: Vec<&str> = [0, 2, 3, 4, 5, 6, 7, 8, 1]
+        .iter()
+        .map(|&i| original_urls[i])
+        .collect();
+    assert_eq!(urls(&har), expected);
+}
@@ -0,0 +1,186 @@
+mod common;
+
+use harper::{
+    find_json,
+    search_for::{self, NeedleMatch},
+    where_param,
+};
+
+fn request_nums(results: &[search_for::SearchResult]) -> Vec<usize> {
+    results.iter().map(|result| result.request_num).collect()
+}
+
+#[test]
+fn finds_string_in_fields() {
+    let har = common::load("multi_page.har");
+    let results = search_for::search_for(&har, "abc123");
+
+    assert_eq!(request_nums(&results), [1, 7]);
+    assert_eq!(
+        results[0].in_fields,
+        ["response_headers", "response_cookies"]
+    );
+    assert_eq!(results[1].in_fields, ["request_headers", "request_cookies"]);
+}
+
+#[test]
+fn finds_json_body_params() {
+    let har = common::load("multi_page.har");
+    let results = search_for::search_for(&har, "SPRING24");
+
+    assert_eq!(request_nums(&results), [8]);
+    assert!(results[0]
+        .in_fields
+        .contains(&"request_body_param".to_string()));
+}
+
+#[test]
+fn any_needle_matches() {
+    let har = common::load("multi_page.har");
+    let results = search_for::search_for_needles(&har, &["ord_789", "Widget"], NeedleMatch::Any);
+
+    assert_eq!(request_nums(&results), [5, 8]);
+    assert_eq!(results[0].needles, ["Widget"]);
+    assert_eq!(results[1].needles, ["ord_789"]);
+}
+
+#[test]
+fn all_needles_must_match() {
+    let har = common::load("multi_page.har");
+    let results =
+        search_for::search_for_needles(&har, &["s3cr3t-token", "coupon"], NeedleMatch::All);
+
+    assert_eq!(request_nums(&results), [8]);
+    assert_eq!(results[0].needles, ["s3cr3t-token", "coupon"]);
+}
+
+#[test]
+fn missing_lists_unmatched_requests() {
+    let har = common::load("multi_page.har");
+    let results = search_for::search_for_missing(&har, &["cdn.example.net"], NeedleMatch::Any);
+
+    assert_eq!(request_nums(&results), [1, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn fuzzy_matches_near_hosts() {
+    let har = common::load("multi_page.har");
+    let matches = search_for::fuzzy_hosts(&har, "exampel.com", 2);
+
+    let hosts: Vec<&str> = matches.iter().map(|m| m.host.as_str()).collect();
+    assert_eq!(hosts, ["api.example.com", "shop.example.com"]);
+    assert!(matches.iter().all(|m| m.distance == 2));
+}
+
+#[test]
+fn templates_render_fields() {
+    let har = common::load("multi_page.har");
+    let results = search_for::search_for(&har, "ord_789");
+    let template: search_for::ResultTemplate = "{request_num} {method} {url}".parse().unwrap();
+
+    assert_eq!(
+        template.render(&results[0]),
+        "8 POST https://api.example.com/v1/orders"
+    );
+}
+
+#[test]
+fn json_path_matches_only_the_value_at_the_path() {
+    let har = common::load("multi_page.har");
+    let path: search_for::JsonPath = "$.order.id".parse().unwrap();
+
+    let results = search_for::search_json_path(&har, &path, &["ord_"], NeedleMatch::Any);
+    assert_eq!(request_nums(&results), [8]);
+    assert_eq!(results[0].in_fields, ["response_content_json"]);
+
+    // present elsewhere in the body, but not at the path
+    let results = search_for::search_json_path(&har, &path, &["pending"], NeedleMatch::Any);
+    assert!(results.is_empty());
+
+    let path: search_for::JsonPath = "$.products[*].name".parse().unwrap();
+    let results = search_for::search_json_path(&har, &path, &["Widget"], NeedleMatch::Any);
+    assert_eq!(request_nums(&results), [5]);
+
+    let path: search_for::JsonPath = "quantity".parse().unwrap();
+    let results = search_for::search_json_path(&har, &path, &["2"], NeedleMatch::Any);
+    assert_eq!(results[0].in_fields, ["request_body_json"]);
+
+    let missing = search_for::search_json_path_missing(&har, &path, &["2"], NeedleMatch::Any);
+    assert_eq!(missing.len(), har.log.entries.len() - 1);
+}
+
+#[test]
+fn json_paths_parse() {
+    let value: serde_json::Value =
+        serde_json::from_str(r#"{"data": {"items": [{"token": "a"}, {"token": "b"}]}}"#).unwrap();
+
+    let select = |path: &str| {
+        let path: search_for::JsonPath = path.parse().unwrap();
+        path.select(&value).into_iter().cloned().collect::<Vec<_>>()
+    };
+    assert_eq!(select("$.data.items[1].token"), ["b"]);
+    assert_eq!(select("data.items[*].token"), ["a", "b"]);
+    assert_eq!(select("$.data.*[0].token"), ["a"]);
+    assert!(select("$.data.missing").is_empty());
+
+    for invalid in ["$.data[", "$.items[x]", "$..data", "$data"] {
+        assert!(
+            invalid.parse::<search_for::JsonPath>().is_err(),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn finds_json_keys_at_any_depth() {
+    let matches: Vec<(usize, &str, String, String)> =
+        find_json::find_json(&common::load("quirks.har"), "id")
+            .into_iter()
+            .map(|m| (m.request_num, m.body, m.path, m.value))
+            .collect();
+
+    // in a base64url encoded body, nested under an array
+    assert_eq!(
+        matches,
+        [
+            (
+                2,
+                "response",
+                "items[0].id".to_string(),
+                r#""a>>?""#.to_string()
+            ),
+            (
+                2,
+                "response",
+                "items[0].owner.id".to_string(),
+                "7".to_string()
+            ),
+        ]
+    );
+
+    let matches = find_json::find_json(&common::load("multi_page.har"), "coupon");
+    assert_eq!(matches.len(), 1);
+    assert_eq!((matches[0].request_num, matches[0].body), (8, "request"));
+    assert_eq!(matches[0].value, r#""SPRING24""#);
+}
+
+#[test]
+fn where_param_matches_name_and_optional_value() {
+    let har = common::load("multi_page.har");
+
+    let matches = where_param::where_param(&har, "page", None);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(
+        (matches[0].request_num, matches[0].value.as_str()),
+        (5, "1")
+    );
+
+    assert_eq!(
+        where_param::where_param(&har, "event", Some("checkout")).len(),
+        1
+    );
+    assert!(where_param::where_param(&har, "event", Some("home")).is_empty());
+    // names are case-sensitive
+    assert!(where_param::where_param(&har, "Page", None).is_empty());
+}